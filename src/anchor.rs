@@ -0,0 +1,15 @@
+/// A position on a grid reference's cell, such as a corner or its centre.
+/// Used to choose where a point, like a map label, is anchored.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Anchor {
+    /// The 'South West' corner - the cell's origin.
+    SW,
+    /// The 'North West' corner.
+    NW,
+    /// The 'North East' corner.
+    NE,
+    /// The 'South East' corner.
+    SE,
+    /// The cell's centre.
+    Centre,
+}
@@ -0,0 +1,152 @@
+use crate::constants::_100KM;
+use crate::{Error, Precision, OSGB};
+
+/// Builder for assembling a British National Grid Reference
+/// from its parts: the 100km square, the eastings and northings
+/// within that square, and the precision.
+///
+/// # Example
+/// ```
+/// use gridish::{OsgbBuilder, Precision};
+///
+/// let gridref = OsgbBuilder::default()
+///     .square("SO")
+///     .eastings(89_200)
+///     .northings(43_700)
+///     .precision(Precision::_100M)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(gridref.to_string(), "SO892437".to_string());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct OsgbBuilder {
+    square: Option<String>,
+    eastings: u32,
+    northings: u32,
+    precision: Option<Precision>,
+}
+
+impl OsgbBuilder {
+    /// Sets the 100km square, such as "SO".
+    pub fn square(mut self, square: &str) -> Self {
+        self.square = Some(square.to_string());
+        self
+    }
+
+    /// Sets the eastings within the 100km square, in metres.
+    pub fn eastings(mut self, eastings: u32) -> Self {
+        self.eastings = eastings;
+        self
+    }
+
+    /// Sets the northings within the 100km square, in metres.
+    pub fn northings(mut self, northings: u32) -> Self {
+        self.northings = northings;
+        self
+    }
+
+    /// Sets the precision.
+    pub fn precision(mut self, precision: Precision) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Builds the grid reference.
+    ///
+    /// # Errors
+    /// Returns an error if the square or precision is missing
+    /// or invalid, if the eastings or northings fall outside of
+    /// the 100km square, or if they are finer than the precision.
+    pub fn build(&self) -> Result<OSGB, Error> {
+        let square = self
+            .square
+            .as_ref()
+            .ok_or_else(|| Error::ParseError("A 100km square is required.".to_string()))?;
+        let precision = self
+            .precision
+            .ok_or_else(|| Error::InvalidPrecision("A precision is required.".to_string()))?;
+
+        let origin: OSGB = square.parse()?;
+        if origin.precision() != Precision::_100Km {
+            return Err(Error::ParseError(format!(
+                "{square} is not a valid 100km square."
+            )));
+        }
+
+        if self.eastings >= _100KM || self.northings >= _100KM {
+            return Err(Error::OutOfBounds);
+        }
+
+        if !self.eastings.is_multiple_of(precision.metres())
+            || !self.northings.is_multiple_of(precision.metres())
+        {
+            return Err(Error::InvalidPrecision(format!(
+                "Eastings and northings are finer than {precision:?}."
+            )));
+        }
+
+        OSGB::new(
+            origin.sw().x() as u32 + self.eastings,
+            origin.sw().y() as u32 + self.northings,
+            precision,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Error, OsgbBuilder, Precision};
+
+    #[test]
+    fn builds_valid_gridref() {
+        let gridref = OsgbBuilder::default()
+            .square("ns")
+            .eastings(24_000)
+            .northings(68_000)
+            .precision(Precision::_1Km)
+            .build()
+            .unwrap();
+
+        assert_eq!(gridref.to_string(), "NS2468");
+    }
+
+    #[test]
+    fn rejects_invalid_parts() {
+        let builder = OsgbBuilder::default()
+            .eastings(24_000)
+            .northings(68_000)
+            .precision(Precision::_1Km);
+
+        assert_eq!(
+            builder.build(),
+            Err(Error::ParseError("A 100km square is required.".to_string()))
+        );
+        assert_eq!(
+            builder.clone().square("NS24").build(),
+            Err(Error::ParseError(
+                "NS24 is not a valid 100km square.".to_string()
+            ))
+        );
+        assert_eq!(
+            builder.clone().square("NS").eastings(100_000).build(),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .square("NS")
+                .precision(Precision::_10Km)
+                .build(),
+            Err(Error::InvalidPrecision(
+                "Eastings and northings are finer than _10Km.".to_string()
+            ))
+        );
+        assert_eq!(
+            OsgbBuilder::default().square("NS").build(),
+            Err(Error::InvalidPrecision(
+                "A precision is required.".to_string()
+            ))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 pub const _500KM: u32 = 500_000;
 pub const _100KM: u32 = 100_000;
 pub const _10KM: u32 = 10_000;
+pub const _5KM: u32 = 5_000;
 pub const _2KM: u32 = 2_000;
 pub const _1KM: u32 = 1_000;
 pub const _100M: u32 = 100;
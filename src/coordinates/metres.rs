@@ -1,10 +1,11 @@
 use crate::constants::*;
 use crate::Error;
 use crate::Precision;
+use std::fmt::Display;
 
 /// A type wrapping u32 to allow bounds checking
 /// and remapping to different precisions.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct Metres(u32);
 
 impl Metres {
@@ -14,22 +15,49 @@ impl Metres {
         Self(self.0 - remainder)
     }
 
+    /// Rounds to the nearest multiple of the precision, with
+    /// ties rounding up. Unlike `precision`, which floors.
+    ///
+    /// # Errors
+    /// Returns an error if rounding up takes the value out of bounds.
+    pub fn to_precision_rounded(self, precision: Precision) -> Result<Self, Error> {
+        let remainder = self.0 % precision.metres();
+
+        if remainder * 2 >= precision.metres() {
+            (self.0 - remainder + precision.metres()).try_into()
+        } else {
+            Ok(Self(self.0 - remainder))
+        }
+    }
+
     pub fn inner(&self) -> u32 {
         self.0
     }
 
     /// Returns the number of metres within the current 100Km square.
-    /// Padded out to a valid grid reference format.
-    pub fn padded(&self, precision: Precision) -> String {
-        if precision.digits() == 0 {
-            return "".to_string();
+    /// Padded out to a valid grid reference format when displayed.
+    pub fn padded(&self, precision: Precision) -> Padded {
+        Padded {
+            value: (self.0 % _100KM) / precision.metres(),
+            width: precision.digits() / 2,
+        }
+    }
+}
+
+/// Digits of a grid reference, zero padded to a fixed width.
+/// Displayed without allocating, so grid references can be
+/// written into fixed size buffers.
+pub struct Padded {
+    value: u32,
+    width: usize,
+}
+
+impl Display for Padded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.width == 0 {
+            Ok(())
         } else {
-            let metres = self.0 % _100KM;
-            format!(
-                "{:0width$}",
-                metres / precision.metres(),
-                width = precision.digits() / 2
-            )
+            write!(f, "{:0width$}", self.value, width = self.width)
         }
     }
 }
@@ -46,15 +74,15 @@ impl TryFrom<u32> for Metres {
     }
 }
 
-impl Into<u32> for Metres {
-    fn into(self) -> u32 {
-        self.0
+impl From<Metres> for u32 {
+    fn from(val: Metres) -> Self {
+        val.0
     }
 }
 
-impl Into<f64> for Metres {
-    fn into(self) -> f64 {
-        f64::from(self.0)
+impl From<Metres> for f64 {
+    fn from(val: Metres) -> Self {
+        f64::from(val.0)
     }
 }
 
@@ -97,6 +125,33 @@ mod test {
         }
     }
 
+    #[test]
+    fn rounds_to_precision() {
+        let values = [
+            (23_449, Precision::_100M, 23_400),
+            (23_450, Precision::_100M, 23_500),
+            (23_451, Precision::_100M, 23_500),
+            (24_999, Precision::_10Km, 20_000),
+            (25_000, Precision::_10Km, 30_000),
+            (25_001, Precision::_10Km, 30_000),
+            (23_480, Precision::_1M, 23_480),
+        ];
+
+        for value in values {
+            let metres: Metres = value.0.try_into().unwrap();
+            let rounded = metres.to_precision_rounded(value.1).unwrap();
+
+            assert_eq!(Into::<u32>::into(rounded), value.2);
+        }
+
+        // Rounding up beyond the final allowed value is rejected.
+        let metres: Metres = (_500KM - 1).try_into().unwrap();
+        assert_eq!(
+            metres.to_precision_rounded(Precision::_1Km),
+            Err(Error::OutOfBounds)
+        );
+    }
+
     #[test]
     fn adds_correct_padding() {
         // Test zero metres
@@ -111,7 +166,7 @@ mod test {
         ];
 
         for value in values {
-            assert_eq!(value.0, metres.padded(value.1));
+            assert_eq!(value.0, metres.padded(value.1).to_string());
         }
 
         // Test 250 metres
@@ -126,7 +181,7 @@ mod test {
         ];
 
         for value in values {
-            assert_eq!(value.0, metres.padded(value.1));
+            assert_eq!(value.0, metres.padded(value.1).to_string());
         }
     }
 }
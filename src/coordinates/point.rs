@@ -5,6 +5,8 @@ use crate::{utils, Error, Precision};
 use std::fmt::Display;
 use std::str::FromStr;
 
+#[cfg(feature = "pentads")]
+use crate::grid::{coords_to_pentad, pentad_to_coords};
 #[cfg(feature = "tetrads")]
 use crate::grid::{coords_to_tetrad, tetrad_to_coords};
 
@@ -13,7 +15,7 @@ use crate::grid::{coords_to_tetrad, tetrad_to_coords};
 /// on a 500km grid at up to 1m precision.
 /// Made up of eastings, northings, and the precision.
 ///
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Point {
     eastings: Metres,
     northings: Metres,
@@ -43,6 +45,45 @@ impl Point {
     pub fn precision(&self) -> Precision {
         self.precision
     }
+
+    /// Returns the length of the point's string representation:
+    /// the 100km square letter, the digits, and any tetrad letter
+    /// or pentad quadrant.
+    pub fn display_len(&self) -> usize {
+        #[cfg(feature = "pentads")]
+        if self.precision == Precision::_5Km {
+            return 1 + self.precision.digits() + 2;
+        }
+
+        #[cfg(feature = "tetrads")]
+        if self.precision == Precision::_2Km {
+            return 1 + self.precision.digits() + 1;
+        }
+
+        1 + self.precision.digits()
+    }
+}
+
+impl Point {
+    /// Parses a tetrad written with its letter before the digits,
+    /// as in "NR24" for "N24R". Returns `None` if the string is
+    /// not in that form, leaving it to be parsed as usual.
+    ///
+    /// Only OSGB grid references accept this form, after their 500km
+    /// letter, as a lone OSI "NR24" would hide an OSGB 10km square.
+    #[cfg(feature = "tetrads")]
+    pub fn from_reversed_tetrad(s: &str) -> Option<Result<Self, Error>> {
+        let bytes = s.as_bytes();
+        if s.len() == 4
+            && s.is_ascii()
+            && bytes[1].is_ascii_alphabetic()
+            && bytes[2..].iter().all(u8::is_ascii_digit)
+        {
+            return Some(format!("{}{}{}", &s[..1], &s[2..], &s[1..2]).parse());
+        }
+
+        None
+    }
 }
 
 impl FromStr for Point {
@@ -56,6 +97,24 @@ impl FromStr for Point {
                 let eastings = column as u32 * _100KM;
                 let northings = row as u32 * _100KM;
 
+                // Special case for Pentads, as in "O84NE".
+                #[cfg(feature = "pentads")]
+                if s.len() == 5 && s.is_ascii() && s[3..].bytes().all(|b| b.is_ascii_alphabetic()) {
+                    // Get Pentad quadrant
+                    let (column, row) = pentad_to_coords(&s[3..])?;
+                    let eastings = eastings + (column as u32 * _5KM);
+                    let northings = northings + (row as u32 * _5KM);
+
+                    // Calculate digits
+                    let (east, north, _precision) = utils::digits(&s[1..3])?;
+
+                    return Ok(Self {
+                        eastings: (eastings + east).try_into()?,
+                        northings: (northings + north).try_into()?,
+                        precision: Precision::_5Km,
+                    });
+                }
+
                 // Special case for Tetrads
                 #[cfg(feature = "tetrads")]
                 if s.len() == 4 {
@@ -86,7 +145,7 @@ impl FromStr for Point {
                     precision,
                 })
             }
-            None => Err(Error::ParseError("String can not be empty.".to_string())),
+            None => Err(Error::Empty),
         }
     }
 }
@@ -102,6 +161,25 @@ impl Display for Point {
         // Unwrapping here as metres are type checked to fit into bounds.
         let letter = coords_to_square(column, row).unwrap();
 
+        // Special case for Pentads
+        #[cfg(feature = "pentads")]
+        if self.precision == Precision::_5Km {
+            // Determine pentad.
+            let pentad_column = ((eastings % _10KM) / _5KM) as usize;
+            let pentad_row = ((northings % _10KM) / _5KM) as usize;
+            // Unwrapping here as metres are type checked to fit into bounds.
+            let pentad = coords_to_pentad(pentad_column, pentad_row).unwrap();
+
+            return write!(
+                f,
+                "{}{}{}{}",
+                letter,
+                self.eastings.padded(Precision::_10Km),
+                self.northings.padded(Precision::_10Km),
+                pentad
+            );
+        }
+
         // Special case for Tetrads
         #[cfg(feature = "tetrads")]
         if self.precision == Precision::_2Km {
@@ -142,7 +220,7 @@ mod test {
         precision: Precision,
     }
 
-    const VALID_POINTS: [(&'static str, TestPoint); 2] = [
+    const VALID_POINTS: [(&str, TestPoint); 2] = [
         (
             "N",
             TestPoint {
@@ -192,6 +270,7 @@ mod test {
             let grid_point = Point::new(eastings, northings, point.1.precision);
 
             assert_eq!(grid_point.to_string(), point.0);
+            assert_eq!(grid_point.display_len(), point.0.len());
         }
     }
 }
@@ -238,6 +317,20 @@ mod test_tetrad {
         }
     }
 
+    #[test]
+    fn parses_reversed_tetrads() {
+        for point in VALID_TETRADS {
+            let reversed = format!("{}{}{}", &point.0[..1], &point.0[3..], &point.0[1..3]);
+            let grid_point = Point::from_reversed_tetrad(&reversed).unwrap().unwrap();
+
+            assert_eq!(grid_point, point.0.parse().unwrap());
+            assert_eq!(grid_point.to_string(), point.0);
+            assert!(reversed.parse::<Point>().is_err());
+        }
+
+        assert!(Point::from_reversed_tetrad("N24R").is_none());
+    }
+
     #[test]
     fn prints_valid_strings() {
         for point in VALID_TETRADS {
@@ -246,6 +339,68 @@ mod test_tetrad {
             let grid_point = Point::new(eastings, northings, point.1.precision);
 
             assert_eq!(grid_point.to_string(), point.0);
+            assert_eq!(grid_point.display_len(), point.0.len());
+        }
+    }
+}
+
+#[cfg(feature = "pentads")]
+#[cfg(test)]
+mod test_pentad {
+    use crate::coordinates::point::Point;
+    use crate::precision::Precision;
+
+    struct TestPoint {
+        eastings: u32,
+        northings: u32,
+        precision: Precision,
+    }
+
+    const VALID_PENTADS: [(&str, TestPoint); 2] = [
+        (
+            "L03SW",
+            TestPoint {
+                eastings: 0,
+                northings: 230_000,
+                precision: Precision::_5Km,
+            },
+        ),
+        (
+            "N24NE",
+            TestPoint {
+                eastings: 225_000,
+                northings: 245_000,
+                precision: Precision::_5Km,
+            },
+        ),
+    ];
+
+    #[test]
+    fn parses_valid_pentads() {
+        for point in VALID_PENTADS {
+            let grid_point: Point = point.0.parse().unwrap();
+
+            assert_eq!(grid_point.eastings.inner(), point.1.eastings);
+            assert_eq!(grid_point.northings.inner(), point.1.northings);
+            assert_eq!(grid_point.precision, point.1.precision);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_pentads() {
+        assert!("N24NN".parse::<Point>().is_err());
+        assert!("N2ANE".parse::<Point>().is_err());
+    }
+
+    #[test]
+    fn prints_valid_pentads() {
+        for point in VALID_PENTADS {
+            let eastings = point.1.eastings.try_into().unwrap();
+            let northings = point.1.northings.try_into().unwrap();
+            let grid_point = Point::new(eastings, northings, point.1.precision);
+
+            assert_eq!(grid_point.to_string(), point.0);
+            assert_eq!(grid_point.display_len(), point.0.len());
         }
     }
 }
@@ -1,14 +1,66 @@
+/// Errors returned when parsing or constructing grid references.
+///
+/// The `Display` output of each variant is stable, and may be relied
+/// upon in tests. The `Debug` output is intended for developers only,
+/// and may change between releases.
+///
+/// New variants may be added without a major release, so matches
+/// on this enum outside of the crate need a wildcard arm.
 #[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
+    /// The string could not be parsed as a grid reference.
+    /// Displayed as `Error parsing string: {message}`.
     ParseError(String),
+    /// The grid reference's precision is not supported or expected.
+    /// Displayed as `Invalid precision: {message}`.
     InvalidPrecision(String),
+    /// The string was empty, or contained only whitespace and separators.
+    /// Displayed as `String can not be empty`.
+    Empty,
+    /// The coordinates lie outside of the grid.
+    /// Displayed as `Coordinates out of bounds`.
     OutOfBounds,
+    /// The buffer is too small to hold the grid reference,
+    /// which needs the given number of bytes.
+    /// Displayed as `Buffer too small: {needed} bytes needed`.
+    BufferTooSmall(usize),
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Error parsing string")
+        match self {
+            Error::ParseError(message) => write!(f, "Error parsing string: {message}"),
+            Error::InvalidPrecision(message) => write!(f, "Invalid precision: {message}"),
+            Error::Empty => write!(f, "String can not be empty"),
+            Error::OutOfBounds => write!(f, "Coordinates out of bounds"),
+            Error::BufferTooSmall(needed) => write!(f, "Buffer too small: {needed} bytes needed"),
+        }
     }
 }
 
 impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod test {
+    use crate::Error;
+
+    #[test]
+    fn displays_stable_strings() {
+        assert_eq!(
+            Error::ParseError("1 is not a valid grid square.".to_string()).to_string(),
+            "Error parsing string: 1 is not a valid grid square."
+        );
+        assert_eq!(
+            Error::InvalidPrecision("Expected precision _100M, found _1Km.".to_string())
+                .to_string(),
+            "Invalid precision: Expected precision _100M, found _1Km."
+        );
+        assert_eq!(Error::Empty.to_string(), "String can not be empty");
+        assert_eq!(Error::OutOfBounds.to_string(), "Coordinates out of bounds");
+        assert_eq!(
+            Error::BufferTooSmall(8).to_string(),
+            "Buffer too small: 8 bytes needed"
+        );
+    }
+}
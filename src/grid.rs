@@ -11,11 +11,11 @@ const GRID: [char; 25] = [
 ];
 
 pub fn square_to_coords(square: &char) -> Result<(usize, usize), Error> {
-    Ok(grid_to_coords(square, &GRID)?)
+    grid_to_coords(square, &GRID)
 }
 
 pub fn coords_to_square(column: usize, row: usize) -> Result<char, Error> {
-    Ok(coords_to_grid(column, row, &GRID)?)
+    coords_to_grid(column, row, &GRID)
 }
 
 /// Return the coordinates of the given grid square.
@@ -40,7 +40,7 @@ fn coords_to_grid(column: usize, row: usize, grid: &[char]) -> Result<char, Erro
     } else {
         let index = column + (GRID_WIDTH * row);
 
-        Ok(*grid.get(index).ok_or_else(|| Error::OutOfBounds)?)
+        Ok(*grid.get(index).ok_or(Error::OutOfBounds)?)
     }
 }
 
@@ -61,6 +61,33 @@ pub fn coords_to_tetrad(column: usize, row: usize) -> Result<char, Error> {
     Ok(coords_to_grid(column, row, &TETRAD_GRID)?)
 }
 
+/// The quadrant suffixes used for pentad coordinates.
+#[cfg(feature = "pentads")]
+const PENTAD_GRID: [&str; 4] = ["SW", "SE", "NW", "NE"];
+
+/// Return the coordinates of the given pentad quadrant.
+/// This is zero-based, so NW => (0, 1);
+#[cfg(feature = "pentads")]
+pub fn pentad_to_coords(quadrant: &str) -> Result<(usize, usize), Error> {
+    let index = PENTAD_GRID
+        .iter()
+        .position(|x| *x == quadrant)
+        .ok_or_else(|| Error::ParseError(format!("{quadrant} is not a valid pentad.")))?;
+
+    Ok((index % 2, index / 2))
+}
+
+/// Returns the pentad quadrant of the given coordinates.
+/// This is zero-based, so (1, 0) => SE;
+#[cfg(feature = "pentads")]
+pub fn coords_to_pentad(column: usize, row: usize) -> Result<&'static str, Error> {
+    if column >= 2 || row >= 2 {
+        Err(Error::OutOfBounds)
+    } else {
+        Ok(PENTAD_GRID[column + (2 * row)])
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
@@ -210,3 +237,43 @@ mod test_tetrad {
         }
     }
 }
+
+#[cfg(feature = "pentads")]
+#[cfg(test)]
+mod test_pentad {
+    use crate::grid::{coords_to_pentad, pentad_to_coords};
+    use crate::Error;
+
+    const VALID_PENTADS: [(&str, (usize, usize)); 4] = [
+        ("SW", (0, 0)),
+        ("SE", (1, 0)),
+        ("NW", (0, 1)),
+        ("NE", (1, 1)),
+    ];
+
+    #[test]
+    fn valid_quadrants_return_coords_pentad() {
+        for quadrant in VALID_PENTADS {
+            assert_eq!(pentad_to_coords(quadrant.0), Ok(quadrant.1));
+            assert_eq!(
+                coords_to_pentad(quadrant.1 .0, quadrant.1 .1),
+                Ok(quadrant.0)
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_quadrants_are_rejected_pentad() {
+        for quadrant in ["ne", "N", "EN", "NEE"] {
+            assert_eq!(
+                pentad_to_coords(quadrant),
+                Err(Error::ParseError(format!(
+                    "{quadrant} is not a valid pentad."
+                )))
+            );
+        }
+
+        assert_eq!(coords_to_pentad(2, 0), Err(Error::OutOfBounds));
+        assert_eq!(coords_to_pentad(0, 2), Err(Error::OutOfBounds));
+    }
+}
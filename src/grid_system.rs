@@ -0,0 +1,60 @@
+use crate::{OSGB, OSI};
+
+/// The national grid a grid reference belongs to.
+///
+/// Grid references on both grids share the same letter alphabet,
+/// but OSGB grid references have two leading letters and OSI grid
+/// references one. This is used to tell them apart once parsed.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum GridSystem {
+    /// The British National Grid, see [`crate::OSGB`].
+    Osgb,
+    /// The Irish Grid, see [`crate::OSI`].
+    Osi,
+}
+
+/// Returns true if the string parses as both an OSGB and an OSI
+/// grid reference, so it can not be placed on a grid by itself.
+///
+/// Each grid's strings are distinct, as OSGB grid references have
+/// two leading letters and OSI grid references one. Tetrads written
+/// with their letter before the digits are only accepted after an
+/// OSGB 500km letter, so "NR24" reads as an OSGB 10km square alone.
+///
+/// # Example
+/// ```
+/// use gridish::is_ambiguous;
+///
+/// assert!(!is_ambiguous("SO892437"));
+/// assert!(!is_ambiguous("O892437"));
+/// ```
+pub fn is_ambiguous(s: &str) -> bool {
+    s.parse::<OSGB>().is_ok() && s.parse::<OSI>().is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::is_ambiguous;
+
+    #[test]
+    fn distinguishes_grid_strings() {
+        assert!(!is_ambiguous("SO892437"));
+        assert!(!is_ambiguous("SO"));
+        assert!(!is_ambiguous("O892437"));
+        assert!(!is_ambiguous("O"));
+        assert!(!is_ambiguous(""));
+    }
+}
+
+#[cfg(feature = "tetrads")]
+#[cfg(test)]
+mod test_tetrad {
+    use crate::is_ambiguous;
+
+    #[test]
+    fn distinguishes_tetrad_strings() {
+        // An OSGB 10km square, not a reversed OSI tetrad.
+        assert!(!is_ambiguous("NR24"));
+        assert!(!is_ambiguous("N24R"));
+    }
+}
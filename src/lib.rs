@@ -21,7 +21,9 @@
 //! ## Features
 //! - `serde`: Provides support for (de)serialization using serde.
 //! - `tetrads`: Provides support for tetrad grid references in the [DINTY](https://web.archive.org/web/20110527152140/http://www.kmbrc.org.uk/recording/help/gridrefhelp.php?page=6)
-//! format, as commonly used in biological surveys.
+//!   format, as commonly used in biological surveys.
+//! - `pentads`: Provides support for 5km pentad grid references, written as a
+//!   10km grid reference followed by the quadrant, such as "SO84NE".
 //!
 //! ```
 //! # #[cfg(feature = "tetrads")]
@@ -37,16 +39,36 @@
 //! # }
 //! ```
 
+mod anchor;
+mod builder;
 mod constants;
 mod coordinates;
 mod error;
 mod grid;
+mod grid_system;
+mod lines;
 mod osgb;
 mod osi;
 mod precision;
+mod sets;
 mod utils;
+#[cfg(feature = "serde")]
+mod with_precision;
 
+pub use anchor::Anchor;
+pub use builder::OsgbBuilder;
 pub use error::Error;
+pub use grid_system::{is_ambiguous, GridSystem};
+pub use lines::{parse_lines_osgb, parse_lines_osi};
 pub use osgb::OSGB;
 pub use osi::OSI;
-pub use precision::Precision;
+pub use precision::{precision_from_digits_str, tetrads_enabled, Precision};
+pub use sets::{
+    ascii_grid_osgb, ascii_grid_osi, boundary_cells_osgb, boundary_cells_osi,
+    bounding_region_mixed_osgb, bounding_region_mixed_osi, coalesce_osgb, coalesce_osi,
+    coarse_cover_osgb, coarse_cover_osi, densify_path_osgb, densify_path_osi, difference_sets_osgb,
+    difference_sets_osi, find_gaps_osgb, find_gaps_osi, interior_cells_osgb, interior_cells_osi,
+    intersect_sets_osgb, intersect_sets_osi, squares_in_rect_osgb, squares_in_rect_osi,
+};
+#[cfg(feature = "serde")]
+pub use with_precision::{GridRef, WithPrecision};
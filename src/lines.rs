@@ -0,0 +1,87 @@
+use crate::{Error, OSGB, OSI};
+use std::str::FromStr;
+
+/// Parses each non-blank line of the text as a British National
+/// Grid Reference, returning the line's 1-based number
+/// with its parse result.
+///
+/// # Example
+/// ```
+/// use gridish::parse_lines_osgb;
+///
+/// let gridrefs = parse_lines_osgb("SO892437\n\nNS2468\n");
+///
+/// assert_eq!(gridrefs[0].0, 1);
+/// assert_eq!(gridrefs[1].0, 3);
+/// ```
+pub fn parse_lines_osgb(text: &str) -> Vec<(usize, Result<OSGB, Error>)> {
+    parse_lines(text)
+}
+
+/// Parses each non-blank line of the text as an Irish National
+/// Grid Reference, returning the line's 1-based number
+/// with its parse result.
+///
+/// # Example
+/// ```
+/// use gridish::parse_lines_osi;
+///
+/// let gridrefs = parse_lines_osi("O892437\n\nS2468\n");
+///
+/// assert_eq!(gridrefs[0].0, 1);
+/// assert_eq!(gridrefs[1].0, 3);
+/// ```
+pub fn parse_lines_osi(text: &str) -> Vec<(usize, Result<OSI, Error>)> {
+    parse_lines(text)
+}
+
+fn parse_lines<T: FromStr<Err = Error>>(text: &str) -> Vec<(usize, Result<T, Error>)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(number, line)| (number, line.parse()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse_lines_osgb, parse_lines_osi, Error};
+
+    #[test]
+    fn parses_osgb_lines() {
+        let gridrefs = parse_lines_osgb(" SO892437 \r\n\n  \nSO89@\nns 24 68");
+
+        assert_eq!(gridrefs.len(), 3);
+        assert_eq!(gridrefs[0].0, 1);
+        assert_eq!(gridrefs[0].1.as_ref().unwrap().to_string(), "SO892437");
+        assert_eq!(gridrefs[1].0, 4);
+        assert_eq!(
+            gridrefs[1].1,
+            Err(Error::ParseError(
+                "3 is not a valid number of digits. Supported values: 0, 2, 4, 6, 8, 10."
+                    .to_string()
+            ))
+        );
+        assert_eq!(gridrefs[2].0, 5);
+        assert_eq!(gridrefs[2].1.as_ref().unwrap().to_string(), "NS2468");
+    }
+
+    #[test]
+    fn parses_osi_lines() {
+        let gridrefs = parse_lines_osi("O892437\n\n1234\ns 24 68\n");
+
+        assert_eq!(gridrefs.len(), 3);
+        assert_eq!(gridrefs[0].0, 1);
+        assert_eq!(gridrefs[0].1.as_ref().unwrap().to_string(), "O892437");
+        assert_eq!(gridrefs[1].0, 3);
+        assert_eq!(
+            gridrefs[1].1,
+            Err(Error::ParseError(
+                "1 is not a valid grid square.".to_string()
+            ))
+        );
+        assert_eq!(gridrefs[2].0, 4);
+        assert_eq!(gridrefs[2].1.as_ref().unwrap().to_string(), "S2468");
+    }
+}
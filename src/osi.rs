@@ -1,17 +1,38 @@
-use crate::utils::trim_string;
-use crate::{coordinates::point::Point as GridPoint, Error, Precision};
-use geo_types::{LineString, Point, Polygon};
+use crate::constants::{_100KM, _1KM, _500KM};
+use crate::utils::{
+    axis_tiles, check_character, check_digit_groups, circle_cells, clipped_area, decode_base32,
+    digit_group, distance, encode_base32, line_string_cells, polygon_contains, trim_string,
+    SliceWriter,
+};
+use crate::{coordinates::point::Point as GridPoint, Anchor, Error, GridSystem, Precision};
+use geo_types::{coord, Coord, Geometry, Line, LineString, MultiPolygon, Point, Polygon, Rect};
+use std::cmp::Ordering;
 use std::fmt::Display;
+use std::ops::Add;
 use std::str::FromStr;
 
+/// The width and height of the area covered by the grid, from the origin.
+const WIDTH: u32 = _500KM;
+const HEIGHT: u32 = _500KM;
+
+// Short codes pack each axis into 21 bits, enough for the whole grid,
+// with the precision above them, in 9 base32 characters.
+const SHORT_CODE_AXIS_BITS: u32 = 21;
+const SHORT_CODE_LEN: usize = 9;
+
 /// Type representing a valid Irish National Grid Reference.
 /// Can be instantiated either by parsing from a string or through
 /// a valid set of eastings and northings as coordinates.
 ///
 /// Provides functionality to convert between strings and coordinates,
 /// as well as re-mapping to a new precision.
+///
+/// Coordinates are those of the Irish Grid, with its origin at the south
+/// west corner of the 'V' square. These are not Irish Transverse Mercator
+/// (ITM) coordinates, which use a different projection and have no grid
+/// letters; ITM coordinates must be converted to the Irish Grid first.
 // Works as a simple wrapper around Point, with some additional methods.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct OSI {
     point: GridPoint,
 }
@@ -20,6 +41,10 @@ impl OSI {
     /// Creates a new grid reference from the given coordinates
     /// and precision.
     ///
+    /// The coordinates are floored to the south west corner of
+    /// the cell at the given precision, so an unaligned coordinate
+    /// gives the same cell as parsing that cell's string.
+    ///
     /// # Errors
     /// Returns an error if the given coordinates are out of bounds.
     ///
@@ -44,206 +69,3105 @@ impl OSI {
         })
     }
 
-    /// Recalculates the grid reference to a new precision.
+    /// Creates a new grid reference from strings of the absolute
+    /// eastings and northings, in metres, and the precision.
+    ///
+    /// # Errors
+    /// Returns an error if either string is not a valid number,
+    /// or if the given coordinates are out of bounds.
     ///
     /// # Example
     /// ```
     /// use gridish::{OSI, Precision};
     ///
-    /// let gridref_100m: OSI = "O892437".parse().unwrap();
-    /// let gridref_10k = gridref_100m.recalculate(Precision::_10Km);
+    /// let gridref = OSI::from_absolute("389200", "243700", Precision::_100M).unwrap();
     ///
-    /// assert_eq!("O84".to_string(), gridref_10k.to_string());
+    /// assert_eq!(gridref.to_string(), "O892437".to_string());
     /// ```
-    pub fn recalculate(&self, precision: Precision) -> Self {
-        if precision > self.point.precision() {
-            //
-            self.clone()
-        } else {
-            let point = GridPoint::new(self.point.eastings(), self.point.northings(), precision);
+    pub fn from_absolute(
+        easting_str: &str,
+        northing_str: &str,
+        precision: Precision,
+    ) -> Result<Self, Error> {
+        let eastings = easting_str
+            .trim()
+            .parse()
+            .map_err(|e| Error::ParseError(format!("{:?}", e)))?;
+        let northings = northing_str
+            .trim()
+            .parse()
+            .map_err(|e| Error::ParseError(format!("{:?}", e)))?;
 
-            Self { point }
+        Self::new(eastings, northings, precision)
+    }
+
+    /// Creates a new grid reference from a pixel position in a raster,
+    /// given the grid coordinates of the raster's top left corner and
+    /// the size of each pixel in metres. Pixel rows count downwards,
+    /// as is usual for rasters, so northings decrease as `py` grows.
+    ///
+    /// # Errors
+    /// Returns an error if the pixel lies outside of the grid.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let origin = (389_000.0, 244_000.0);
+    /// let gridref = OSI::from_pixel(250.0, 50.0, origin, 1.0, Precision::_100M).unwrap();
+    ///
+    /// assert_eq!(gridref.to_string(), "O892439".to_string());
+    /// ```
+    pub fn from_pixel(
+        px: f64,
+        py: f64,
+        origin: (f64, f64),
+        resolution: f64,
+        precision: Precision,
+    ) -> Result<Self, Error> {
+        let eastings = origin.0 + px * resolution;
+        let northings = origin.1 - py * resolution;
+
+        if !(eastings.is_finite() && northings.is_finite()) || eastings < 0.0 || northings < 0.0 {
+            return Err(Error::OutOfBounds);
         }
+
+        Self::new(eastings as u32, northings as u32, precision)
     }
 
-    /// Returns the point at the OSI's
-    /// 'South West' corner - its origin.
+    /// Creates a new grid reference from its column and row indices
+    /// into a global raster of cells at the given precision.
+    /// The inverse of [`OSI::global_cell_index`].
+    ///
+    /// # Errors
+    /// Returns an error if the cell is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref = OSI::from_global_cell_index(389, 243, Precision::_1Km).unwrap();
+    ///
+    /// assert_eq!(gridref.to_string(), "O8943".to_string());
+    /// ```
+    pub fn from_global_cell_index(
+        column: u32,
+        row: u32,
+        precision: Precision,
+    ) -> Result<Self, Error> {
+        let eastings = column
+            .checked_mul(precision.metres())
+            .ok_or(Error::OutOfBounds)?;
+        let northings = row
+            .checked_mul(precision.metres())
+            .ok_or(Error::OutOfBounds)?;
+
+        Self::new(eastings, northings, precision)
+    }
+
+    /// Creates a new 1km grid reference from its kilometre indices,
+    /// the eastings and northings from the grid's origin in kilometres,
+    /// as commonly used to key gridded datasets.
+    /// The inverse of [`OSI::km_index`].
+    ///
+    /// # Errors
+    /// Returns an error if the cell is out of bounds.
     ///
     /// # Example
     /// ```
     /// use gridish::OSI;
-    /// use geo_types::coord;
     ///
-    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let gridref = OSI::from_km_index(389, 243).unwrap();
     ///
-    /// assert_eq!(gridref.sw(), coord! {x: 389_200.0, y: 243_700.0 }.into());
+    /// assert_eq!(gridref.to_string(), "O8943".to_string());
     /// ```
-    pub fn sw(&self) -> Point {
-        Point::new(self.point.eastings().into(), self.point.northings().into())
+    pub fn from_km_index(east_km: u32, north_km: u32) -> Result<Self, Error> {
+        Self::from_global_cell_index(east_km, north_km, Precision::_1Km)
     }
 
-    /// Returns the point at the OSI's
-    /// 'North West' corner.
+    /// Parses a grid reference from a string, as with `parse`, but
+    /// rejecting input whose digits are ambiguously grouped. Digits
+    /// written as one group are accepted, but once split by whitespace
+    /// or separators there must be exactly two groups, the eastings and
+    /// northings, of equal length. Input such as "O8 437" or "O8 43 7"
+    /// would otherwise be read as "O8437", which is unlikely to be
+    /// what was meant.
+    ///
+    /// # Errors
+    /// Returns an error if the string can not be parsed, or if its
+    /// digits are split into other than two groups of equal length.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// assert!(OSI::from_str_checked("O84 37").is_ok());
+    /// assert!(OSI::from_str_checked("O8 437").is_err());
+    /// assert!(OSI::from_str_checked("O8 43 7").is_err());
+    /// ```
+    pub fn from_str_checked(s: &str) -> Result<Self, Error> {
+        check_digit_groups(s)?;
+
+        s.parse()
+    }
+
+    /// Returns the OSI as a string followed by a single check
+    /// character, so that mistyped grid references can be detected
+    /// when read back with [`OSI::from_string_with_check`].
+    ///
+    /// The check character is computed over the grid reference's
+    /// string using the Luhn mod N algorithm over `0-9A-Z`: working
+    /// from the right, each character's position in that alphabet is
+    /// alternately doubled, its base 36 digits summed into a total,
+    /// and the check character chosen to make the total a multiple
+    /// of 36.
     ///
     /// # Example
     /// ```
     /// use gridish::OSI;
-    /// use geo_types::coord;
     ///
     /// let gridref: OSI = "O892437".parse().unwrap();
     ///
-    /// assert_eq!(gridref.nw(), coord! {x: 389_200.0, y: 243_800.0 }.into());
+    /// assert_eq!(gridref.to_string_with_check(), "O8924376".to_string());
     /// ```
-    pub fn nw(&self) -> Point {
-        Point::new(
-            self.point.eastings().inner() as f64,
-            (self.point.northings().inner() + self.point.precision().metres()) as f64,
-        )
+    pub fn to_string_with_check(&self) -> String {
+        let string = self.to_string();
+        // Unwrapping here as grid references only contain digits and uppercase letters.
+        let check = check_character(&string).unwrap();
+
+        format!("{string}{check}")
     }
 
-    /// Returns the point at the OSI's
-    /// 'North East' corner.
+    /// Parses a grid reference followed by a single check character,
+    /// as written by [`OSI::to_string_with_check`], validating the check
+    /// character against the parsed grid reference.
+    ///
+    /// # Errors
+    /// Returns an error if the string, without its last character,
+    /// can not be parsed, or if the check character does not match.
     ///
     /// # Example
     /// ```
     /// use gridish::OSI;
-    /// use geo_types::coord;
     ///
-    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let gridref = OSI::from_string_with_check("O8924376").unwrap();
+    /// assert_eq!(gridref.to_string(), "O892437".to_string());
     ///
-    /// assert_eq!(gridref.ne(), coord! {x: 389_300.0, y: 243_800.0 }.into());
+    /// assert!(OSI::from_string_with_check("O8924736").is_err());
     /// ```
-    pub fn ne(&self) -> Point {
-        Point::new(
-            (self.point.eastings().inner() + self.point.precision().metres()) as f64,
-            (self.point.northings().inner() + self.point.precision().metres()) as f64,
-        )
+    pub fn from_string_with_check(s: &str) -> Result<Self, Error> {
+        let string = trim_string(s);
+        let check = string.chars().last().ok_or(Error::Empty)?;
+        let gridref: Self = string[..string.len() - check.len_utf8()].parse()?;
+
+        if check_character(&gridref.to_string()) != Some(check) {
+            return Err(Error::ParseError(format!(
+                "{check} is not the expected check character."
+            )));
+        }
+
+        Ok(gridref)
     }
 
-    /// Returns the point at the OSI's
-    /// 'South East' corner.
+    /// Parses a grid reference from a string, requiring
+    /// it to be of the expected precision.
+    ///
+    /// # Errors
+    /// Returns an error if the string can not be parsed, or if
+    /// the parsed grid reference is of a different precision.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref = OSI::parse_expecting("O892437", Precision::_100M).unwrap();
+    /// assert_eq!(gridref.to_string(), "O892437".to_string());
+    ///
+    /// assert!(OSI::parse_expecting("O8943", Precision::_100M).is_err());
+    /// ```
+    pub fn parse_expecting(s: &str, precision: Precision) -> Result<Self, Error> {
+        let gridref: Self = s.parse()?;
+
+        if gridref.precision() != precision {
+            Err(Error::InvalidPrecision(format!(
+                "Expected precision {:?}, found {:?}.",
+                precision,
+                gridref.precision()
+            )))
+        } else {
+            Ok(gridref)
+        }
+    }
+
+    /// Parses a grid reference from a string, requiring it to lie
+    /// within one of the allowed 100km squares, such as "O".
+    /// The allowed squares are matched ignoring case.
+    ///
+    /// # Errors
+    /// Returns an error if the string can not be parsed, or if the
+    /// parsed grid reference is not within an allowed square.
     ///
     /// # Example
     /// ```
     /// use gridish::OSI;
-    /// use geo_types::coord;
     ///
-    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let allowed = ["O", "N"];
     ///
-    /// assert_eq!(gridref.se(), coord! {x: 389_300.0, y: 243_700.0 }.into());
+    /// assert!(OSI::parse_in_squares("O892437", &allowed).is_ok());
+    /// assert!(OSI::parse_in_squares("M892437", &allowed).is_err());
     /// ```
-    pub fn se(&self) -> Point {
-        Point::new(
-            (self.point.eastings().inner() + self.point.precision().metres()) as f64,
-            self.point.northings().inner() as f64,
-        )
+    pub fn parse_in_squares(s: &str, allowed: &[&str]) -> Result<Self, Error> {
+        let gridref: Self = s.parse()?;
+        let square = gridref.square_ref().to_string();
+
+        if allowed
+            .iter()
+            .any(|allowed| allowed.trim().eq_ignore_ascii_case(&square))
+        {
+            Ok(gridref)
+        } else {
+            Err(Error::ParseError(format!(
+                "{square} is not an allowed grid square."
+            )))
+        }
     }
 
-    /// Returns the point at the OSI's
-    /// centre.
+    /// Parses a grid reference from a string, requiring it to
+    /// be no finer than the given maximum precision.
+    ///
+    /// # Errors
+    /// Returns an error if the string can not be parsed, or if
+    /// the parsed grid reference is finer than the maximum.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref = OSI::parse_max_precision("O8943", Precision::_1Km).unwrap();
+    /// assert_eq!(gridref.to_string(), "O8943".to_string());
+    ///
+    /// assert!(OSI::parse_max_precision("O892437", Precision::_1Km).is_err());
+    /// ```
+    pub fn parse_max_precision(s: &str, max: Precision) -> Result<Self, Error> {
+        let gridref: Self = s.parse()?;
+
+        if gridref.precision() > max {
+            Err(Error::InvalidPrecision(format!(
+                "Expected precision no finer than {:?}, found {:?}.",
+                max,
+                gridref.precision()
+            )))
+        } else {
+            Ok(gridref)
+        }
+    }
+
+    /// Parses a grid reference from a string and recalculates it
+    /// to the given precision in one step. This only ever coarsens:
+    /// a grid reference already at or coarser than the precision
+    /// is returned as parsed. Use [`OSI::parse_expecting`] to
+    /// reject grid references of another precision instead.
+    ///
+    /// # Errors
+    /// Returns an error if the string can not be parsed.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref = OSI::parse_at("O892437", Precision::_1Km).unwrap();
+    /// assert_eq!(gridref.to_string(), "O8943".to_string());
+    ///
+    /// let gridref = OSI::parse_at("O84", Precision::_1Km).unwrap();
+    /// assert_eq!(gridref.to_string(), "O84".to_string());
+    /// ```
+    pub fn parse_at(s: &str, precision: Precision) -> Result<Self, Error> {
+        Ok(s.parse::<Self>()?.recalculate(precision))
+    }
+
+    /// Parses a grid reference from its 100km square, eastings and
+    /// northings given separately. Each group of digits is parsed on its
+    /// own, with the precision implied by its length, allowing recovery
+    /// of references that are not correctly padded.
+    ///
+    /// # Errors
+    /// Returns an error if any part can not be parsed, or if the
+    /// eastings and northings imply different precisions.
     ///
     /// # Example
     /// ```
     /// use gridish::OSI;
-    /// use geo_types::coord;
     ///
-    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let gridref = OSI::parse_unpadded("892", "437", "O").unwrap();
+    /// assert_eq!(gridref.to_string(), "O892437".to_string());
     ///
-    /// assert_eq!(gridref.centre(), coord! {x: 389_250.0, y: 243_750.0 }.into());
+    /// assert!(OSI::parse_unpadded("892", "43", "O").is_err());
     /// ```
-    pub fn centre(&self) -> Point {
-        Point::new(
-            self.point.eastings().inner() as f64 + (self.point.precision().metres() as f64 / 2.0),
-            self.point.northings().inner() as f64 + (self.point.precision().metres() as f64 / 2.0),
+    pub fn parse_unpadded(easting: &str, northing: &str, square: &str) -> Result<Self, Error> {
+        let origin: Self = square.parse()?;
+        if origin.precision() != Precision::_100Km {
+            return Err(Error::ParseError(format!(
+                "{square} is not a valid 100km square."
+            )));
+        }
+
+        let (eastings, easting_precision) = digit_group(easting.trim())?;
+        let (northings, northing_precision) = digit_group(northing.trim())?;
+        if easting_precision != northing_precision {
+            return Err(Error::InvalidPrecision(format!(
+                "Eastings imply {:?} but northings imply {:?}.",
+                easting_precision, northing_precision
+            )));
+        }
+
+        Self::new(
+            origin.eastings() + eastings,
+            origin.northings() + northings,
+            easting_precision,
         )
     }
 
-    /// Returns the OSI's perimeter.
+    /// Parses each of the inputs, separating the grid references that
+    /// parsed successfully from the inputs that failed, with their errors.
     ///
     /// # Example
     /// ```
     /// use gridish::OSI;
-    /// use geo_types::{LineString, Point, Polygon};
     ///
-    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let inputs = ["O892437".to_string(), "123".to_string()];
+    /// let (gridrefs, failures) = OSI::partition_parse(inputs);
     ///
-    /// assert_eq!(
-    ///     gridref.perimeter(),
-    ///     Polygon::new(
-    ///         LineString::from(
-    ///             vec![
-    ///                 Point::new(389_200.0, 243_700.0),
-    ///                 Point::new(389_200.0, 243_800.0),
-    ///                 Point::new(389_300.0, 243_800.0),
-    ///                 Point::new(389_300.0, 243_700.0)
-    ///             ]
-    ///         ),
-    ///         vec![]
-    ///     )
-    /// );
+    /// assert_eq!(gridrefs.len(), 1);
+    /// assert_eq!(failures[0].0, "123".to_string());
     /// ```
-    pub fn perimeter(&self) -> Polygon {
-        Polygon::new(
-            LineString::from(vec![self.sw(), self.nw(), self.ne(), self.se()]),
-            vec![],
-        )
+    pub fn partition_parse<I: IntoIterator<Item = String>>(
+        inputs: I,
+    ) -> (Vec<Self>, Vec<(String, Error)>) {
+        let mut gridrefs = Vec::new();
+        let mut failures = Vec::new();
+
+        for input in inputs {
+            match input.parse() {
+                Ok(gridref) => gridrefs.push(gridref),
+                Err(error) => failures.push((input, error)),
+            }
+        }
+
+        (gridrefs, failures)
     }
 
-    /// Returns the OSI's precision.
+    /// Returns whether the string is a grid reference in its
+    /// canonical form, ignoring any leading or trailing whitespace.
+    /// Valid grid references that are not normalised, such as
+    /// lowercase or spaced strings, are not canonical.
     ///
     /// # Example
     /// ```
-    /// use gridish::{OSI, Precision};
+    /// use gridish::OSI;
+    ///
+    /// assert!(OSI::is_canonical("S2468"));
+    /// assert!(!OSI::is_canonical("s 24 68"));
+    /// ```
+    pub fn is_canonical(s: &str) -> bool {
+        let s = s.trim();
+
+        s.parse::<Self>()
+            .map(|gridref| gridref.to_string() == s)
+            .unwrap_or(false)
+    }
+
+    /// Encodes the OSI into a fixed-size array of bytes,
+    /// with the following layout:
+    ///
+    /// | Bytes  | Contents                  |
+    /// |--------|---------------------------|
+    /// | `0..4` | Eastings, big endian      |
+    /// | `4..8` | Northings, big endian     |
+    /// | `8`    | Precision                 |
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
     ///
     /// let gridref: OSI = "O892437".parse().unwrap();
     ///
-    /// assert_eq!(gridref.precision(), Precision::_100M);
+    /// assert_eq!(OSI::from_bytes(&gridref.to_bytes()), Ok(gridref));
     /// ```
-    pub fn precision(&self) -> Precision {
-        self.point.precision()
-    }
-}
+    pub fn to_bytes(&self) -> [u8; 9] {
+        let mut bytes = [0; 9];
 
-impl FromStr for OSI {
-    type Err = Error;
+        bytes[0..4].copy_from_slice(&self.point.eastings().inner().to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.point.northings().inner().to_be_bytes());
+        bytes[8] = self.point.precision().code();
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let string: String = trim_string(s);
-        let point: GridPoint = string.parse()?;
+        bytes
+    }
 
-        Ok(Self { point })
+    /// Decodes an OSI from an array of bytes,
+    /// as created by [`OSI::to_bytes`].
+    ///
+    /// # Errors
+    /// Returns an error if the coordinates or precision
+    /// encoded in the bytes are not valid.
+    pub fn from_bytes(bytes: &[u8; 9]) -> Result<Self, Error> {
+        // Unwrapping here as the slices are of a fixed length.
+        let eastings = u32::from_be_bytes(bytes[0..4].try_into().unwrap()).try_into()?;
+        let northings = u32::from_be_bytes(bytes[4..8].try_into().unwrap()).try_into()?;
+        let precision = Precision::from_code(bytes[8]).ok_or_else(|| {
+            Error::InvalidPrecision(format!("{} is not a valid precision code.", bytes[8]))
+        })?;
+
+        Ok(Self {
+            point: GridPoint::new(eastings, northings, precision),
+        })
     }
-}
 
-impl Display for OSI {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.point)
+    /// Encodes the OSI as a short code of 9 characters, for
+    /// compact sharing such as in URLs. The code packs the absolute
+    /// eastings, northings, and precision, and is encoded in base32
+    /// without the easily confused letters I, L, O and U.
+    ///
+    /// The layout is frozen: the top 3 of the 45 bits hold the
+    /// precision code, then 21 bits each for eastings and northings.
+    /// All 8 precision codes are now taken, so any further precision
+    /// needs a new, longer code rather than a change to this one.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let code = gridref.to_short_code();
+    ///
+    /// assert_eq!(code.len(), 9);
+    /// assert_eq!(OSI::from_short_code(&code), Ok(gridref));
+    /// ```
+    pub fn to_short_code(&self) -> String {
+        let packed = (u64::from(self.precision().code()) << (SHORT_CODE_AXIS_BITS * 2))
+            | (u64::from(self.eastings()) << SHORT_CODE_AXIS_BITS)
+            | u64::from(self.northings());
+
+        encode_base32(packed, SHORT_CODE_LEN)
     }
-}
 
-#[cfg(test)]
-mod test {
-    use crate::OSI;
-    use geo_types::{LineString, Point, Polygon};
+    /// Decodes an OSI from a short code,
+    /// as created by [`OSI::to_short_code`].
+    /// Letters are accepted in either case.
+    ///
+    /// # Errors
+    /// Returns an error if the code is not 9 valid characters,
+    /// or if the coordinates or precision it encodes are not valid.
+    pub fn from_short_code(s: &str) -> Result<Self, Error> {
+        if s.len() != SHORT_CODE_LEN {
+            return Err(Error::ParseError(format!(
+                "{} is not a valid short code length. Expected {SHORT_CODE_LEN}.",
+                s.len()
+            )));
+        }
 
-    #[test]
-    fn coordinates_are_correct() {
-        let osi = OSI::new(0, 0, crate::Precision::_100M).unwrap();
-        let sw = osi.sw();
-        let nw = osi.nw();
-        let ne = osi.ne();
-        let se = osi.se();
+        let packed = decode_base32(s)?;
+        let mask = (1 << SHORT_CODE_AXIS_BITS) - 1;
+        let code = (packed >> (SHORT_CODE_AXIS_BITS * 2)) as u8;
+        let precision = Precision::from_code(code).ok_or_else(|| {
+            Error::InvalidPrecision(format!("{code} is not a valid precision code."))
+        })?;
 
-        assert_eq!(sw, Point::new(0.0, 0.0));
-        assert_eq!(nw, Point::new(0.0, 100.0));
-        assert_eq!(ne, Point::new(100.0, 100.0));
-        assert_eq!(se, Point::new(100.0, 0.0));
-        assert_eq!(osi.centre(), Point::new(50.0, 50.0));
-        assert_eq!(
-            osi.perimeter(),
-            Polygon::new(LineString::from(vec![sw, nw, ne, se]), vec![])
+        // Casting is safe here as each axis is masked to fit.
+        Self::new(
+            ((packed >> SHORT_CODE_AXIS_BITS) & mask) as u32,
+            (packed & mask) as u32,
+            precision,
         )
     }
+
+    /// Returns true if the OSI's south west corner lies on the
+    /// 2km tetrad grid, meaning recalculating it to `Precision::_2Km`
+    /// would not move it.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let aligned: OSI = "N2642".parse().unwrap();
+    /// let unaligned: OSI = "N2743".parse().unwrap();
+    ///
+    /// assert!(aligned.is_2km_aligned());
+    /// assert!(!unaligned.is_2km_aligned());
+    /// ```
+    #[cfg(feature = "tetrads")]
+    pub fn is_2km_aligned(&self) -> bool {
+        self.point
+            .eastings()
+            .inner()
+            .is_multiple_of(Precision::_2Km.metres())
+            && self
+                .point
+                .northings()
+                .inner()
+                .is_multiple_of(Precision::_2Km.metres())
+    }
+
+    /// Returns how many metres the given eastings and northings
+    /// would each be floored by when creating a grid reference at
+    /// the given precision, as in [`OSI::new`].
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// assert_eq!(OSI::floor_residual(389_290, 243_760, Precision::_100M), (90, 60));
+    /// ```
+    pub fn floor_residual(eastings: u32, northings: u32, precision: Precision) -> (u32, u32) {
+        let metres = precision.metres();
+
+        (eastings % metres, northings % metres)
+    }
+
+    /// Recalculates the grid reference to a new precision.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref_100m: OSI = "O892437".parse().unwrap();
+    /// let gridref_10k = gridref_100m.recalculate(Precision::_10Km);
+    ///
+    /// assert_eq!("O84".to_string(), gridref_10k.to_string());
+    /// ```
+    pub fn recalculate(&self, precision: Precision) -> Self {
+        if precision > self.point.precision() {
+            //
+            self.clone()
+        } else {
+            let point = GridPoint::new(self.point.eastings(), self.point.northings(), precision);
+
+            Self { point }
+        }
+    }
+
+    /// Returns the OSI recalculated to its own precision and
+    /// each coarser one, from finest to `Precision::_100Km`.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let ancestry: Vec<String> = gridref
+    ///     .ancestry()
+    ///     .iter()
+    ///     .map(|gridref| gridref.to_string())
+    ///     .collect();
+    ///
+    /// # #[cfg(not(any(feature = "tetrads", feature = "pentads")))]
+    /// assert_eq!(ancestry, vec!["O892437", "O8943", "O84", "O"]);
+    /// # #[cfg(all(feature = "tetrads", not(feature = "pentads")))]
+    /// # assert_eq!(ancestry, vec!["O892437", "O8943", "O84W", "O84", "O"]);
+    /// # #[cfg(all(feature = "pentads", not(feature = "tetrads")))]
+    /// # assert_eq!(ancestry, vec!["O892437", "O8943", "O84SE", "O84", "O"]);
+    /// # #[cfg(all(feature = "tetrads", feature = "pentads"))]
+    /// # assert_eq!(ancestry, vec!["O892437", "O8943", "O84W", "O84SE", "O84", "O"]);
+    /// ```
+    pub fn ancestry(&self) -> Vec<Self> {
+        Precision::ALL
+            .iter()
+            .rev()
+            .filter(|precision| **precision <= self.precision())
+            .map(|precision| self.recalculate(*precision))
+            .collect()
+    }
+
+    /// Returns the whole 100km square containing the OSI,
+    /// for use as a key when grouping or sorting by square.
+    /// This is the same as recalculating to `Precision::_100Km`.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.square_ref().to_string(), "O".to_string());
+    /// ```
+    pub fn square_ref(&self) -> Self {
+        self.recalculate(Precision::_100Km)
+    }
+
+    /// Returns both the OSI and another recalculated
+    /// to the coarser of their two precisions.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let other: OSI = "O8943".parse().unwrap();
+    /// let (gridref, other) = gridref.align_precision_with(&other);
+    ///
+    /// assert_eq!(gridref, other);
+    /// ```
+    pub fn align_precision_with(&self, other: &Self) -> (Self, Self) {
+        let precision = self.precision().min(other.precision());
+
+        (self.recalculate(precision), other.recalculate(precision))
+    }
+
+    /// Returns the point at the OSI's
+    /// 'South West' corner - its origin.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::coord;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.sw(), coord! {x: 389_200.0, y: 243_700.0 }.into());
+    /// ```
+    pub fn sw(&self) -> Point {
+        Point::new(self.point.eastings().into(), self.point.northings().into())
+    }
+
+    /// Returns the point at the OSI's
+    /// 'North West' corner.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::coord;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.nw(), coord! {x: 389_200.0, y: 243_800.0 }.into());
+    /// ```
+    pub fn nw(&self) -> Point {
+        Point::new(
+            self.point.eastings().inner() as f64,
+            (self.point.northings().inner() + self.point.precision().metres()) as f64,
+        )
+    }
+
+    /// Returns the point at the OSI's
+    /// 'North East' corner.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::coord;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.ne(), coord! {x: 389_300.0, y: 243_800.0 }.into());
+    /// ```
+    pub fn ne(&self) -> Point {
+        Point::new(
+            (self.point.eastings().inner() + self.point.precision().metres()) as f64,
+            (self.point.northings().inner() + self.point.precision().metres()) as f64,
+        )
+    }
+
+    /// Returns the point at the OSI's
+    /// 'South East' corner.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::coord;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.se(), coord! {x: 389_300.0, y: 243_700.0 }.into());
+    /// ```
+    pub fn se(&self) -> Point {
+        Point::new(
+            (self.point.eastings().inner() + self.point.precision().metres()) as f64,
+            self.point.northings().inner() as f64,
+        )
+    }
+
+    /// Returns the point at the OSI's
+    /// centre.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::coord;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.centre(), coord! {x: 389_250.0, y: 243_750.0 }.into());
+    /// ```
+    pub fn centre(&self) -> Point {
+        Point::new(
+            self.point.eastings().inner() as f64 + (self.point.precision().metres() as f64 / 2.0),
+            self.point.northings().inner() as f64 + (self.point.precision().metres() as f64 / 2.0),
+        )
+    }
+
+    /// Returns the point at the given anchor on the OSI,
+    /// such as a corner or its centre.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{Anchor, OSI};
+    /// use geo_types::coord;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.label_anchor(Anchor::NE), coord! {x: 389_300.0, y: 243_800.0 }.into());
+    /// ```
+    pub fn label_anchor(&self, anchor: Anchor) -> Point {
+        match anchor {
+            Anchor::SW => self.sw(),
+            Anchor::NW => self.nw(),
+            Anchor::NE => self.ne(),
+            Anchor::SE => self.se(),
+            Anchor::Centre => self.centre(),
+        }
+    }
+
+    /// Returns the point at the given anchor on the OSI as a
+    /// geometry, for layers made up of points rather than polygons.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{Anchor, OSI};
+    /// use geo_types::{Geometry, Point};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     gridref.to_point_geometry(Anchor::SW),
+    ///     Geometry::Point(Point::new(389_200.0, 243_700.0))
+    /// );
+    /// ```
+    pub fn to_point_geometry(&self, anchor: Anchor) -> Geometry {
+        Geometry::Point(self.label_anchor(anchor))
+    }
+
+    /// Returns the OSI's perimeter as an explicitly closed ring,
+    /// running from the south west corner clockwise and repeating
+    /// the south west corner at the end.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let ring = gridref.ring_closed();
+    ///
+    /// assert_eq!(ring.0.len(), 5);
+    /// assert_eq!(ring.0.first(), ring.0.last());
+    /// ```
+    pub fn ring_closed(&self) -> LineString {
+        LineString::from(vec![self.sw(), self.nw(), self.ne(), self.se(), self.sw()])
+    }
+
+    /// Returns the OSI's perimeter.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::{LineString, Point, Polygon};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     gridref.perimeter(),
+    ///     Polygon::new(
+    ///         LineString::from(
+    ///             vec![
+    ///                 Point::new(389_200.0, 243_700.0),
+    ///                 Point::new(389_200.0, 243_800.0),
+    ///                 Point::new(389_300.0, 243_800.0),
+    ///                 Point::new(389_300.0, 243_700.0)
+    ///             ]
+    ///         ),
+    ///         vec![]
+    ///     )
+    /// );
+    /// ```
+    pub fn perimeter(&self) -> Polygon {
+        Polygon::new(
+            LineString::from(vec![self.sw(), self.nw(), self.ne(), self.se()]),
+            vec![],
+        )
+    }
+
+    /// Returns the OSI's centre as fractions of the width
+    /// and height of the area covered by the grid, from 0 to 1.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "V".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.normalised_centre(), (0.1, 0.1));
+    /// ```
+    pub fn normalised_centre(&self) -> (f64, f64) {
+        let centre = self.centre();
+
+        (
+            centre.x() / f64::from(WIDTH),
+            centre.y() / f64::from(HEIGHT),
+        )
+    }
+
+    /// Returns the OSI's minimum and maximum
+    /// corners, 'South West' and 'North East'.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::coord;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     gridref.coord_bounds(),
+    ///     (
+    ///         coord! { x: 389_200.0, y: 243_700.0 },
+    ///         coord! { x: 389_300.0, y: 243_800.0 }
+    ///     )
+    /// );
+    /// ```
+    pub fn coord_bounds(&self) -> (Coord, Coord) {
+        (self.sw().into(), self.ne().into())
+    }
+
+    /// Returns whether the OSI's centre lies within the given polygon.
+    /// The polygon is assumed to share the grid's coordinate system.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::{LineString, Polygon};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let area = Polygon::new(
+    ///     LineString::from(vec![
+    ///         (389_000.0, 243_000.0),
+    ///         (389_000.0, 244_000.0),
+    ///         (390_000.0, 244_000.0),
+    ///         (390_000.0, 243_000.0),
+    ///     ]),
+    ///     vec![],
+    /// );
+    ///
+    /// assert!(gridref.centre_within(&area));
+    /// ```
+    pub fn centre_within(&self, poly: &Polygon) -> bool {
+        polygon_contains(poly, &self.centre())
+    }
+
+    /// Returns the fraction of the OSI's area lying within the
+    /// given polygon, between 0 and 1.
+    /// The polygon is assumed to share the grid's coordinate system.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::{LineString, Polygon};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let area = Polygon::new(
+    ///     LineString::from(vec![
+    ///         (389_000.0, 243_000.0),
+    ///         (389_000.0, 244_000.0),
+    ///         (389_250.0, 244_000.0),
+    ///         (389_250.0, 243_000.0),
+    ///     ]),
+    ///     vec![],
+    /// );
+    ///
+    /// assert_eq!(gridref.coverage_fraction(&area), 0.5);
+    /// ```
+    pub fn coverage_fraction(&self, poly: &Polygon) -> f64 {
+        let (min, max) = self.coord_bounds();
+        let area = f64::from(self.precision().metres()).powi(2);
+
+        (clipped_area(poly, &Rect::new(min, max)) / area).min(1.0)
+    }
+
+    /// Returns how the OSI's area splits across an external grid of
+    /// square tiles of the given size, whose origin need not match the
+    /// national grid's. Each overlapping tile is given as its column
+    /// and row, counted from the tile origin, with the overlap area in
+    /// square metres, ordered from south to north, then west to east.
+    /// The tiles are assumed to share the grid's coordinate system.
+    /// Returns an empty vector if the tile size is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let overlaps = gridref.tile_overlaps((0.0, 0.0), 500.0);
+    ///
+    /// assert_eq!(overlaps.len(), 4);
+    /// assert_eq!(overlaps[0], (778, 486, 250_000.0));
+    /// ```
+    pub fn tile_overlaps(&self, tile_origin: (f64, f64), tile_size: f64) -> Vec<(i64, i64, f64)> {
+        if !tile_size.is_finite() || tile_size <= 0.0 {
+            return vec![];
+        }
+
+        let (min, max) = self.coord_bounds();
+        let columns = axis_tiles(min.x, max.x, tile_origin.0, tile_size);
+        let rows = axis_tiles(min.y, max.y, tile_origin.1, tile_size);
+
+        rows.iter()
+            .flat_map(|(row, height)| {
+                columns
+                    .iter()
+                    .map(move |(column, width)| (*column, *row, width * height))
+            })
+            .collect()
+    }
+
+    /// Returns whether the OSI lies entirely within the given rect.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::{coord, Rect};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let rect = Rect::new(
+    ///     coord! { x: 389_000.0, y: 243_000.0 },
+    ///     coord! { x: 390_000.0, y: 244_000.0 },
+    /// );
+    ///
+    /// assert!(gridref.within_rect(rect));
+    /// ```
+    pub fn within_rect(&self, rect: Rect) -> bool {
+        let (sw, ne) = (self.sw(), self.ne());
+
+        sw.x() >= rect.min().x
+            && sw.y() >= rect.min().y
+            && ne.x() <= rect.max().x
+            && ne.y() <= rect.max().y
+    }
+
+    /// Returns whether the OSI intersects or
+    /// shares a boundary with the given rect.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::{coord, Rect};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let rect = Rect::new(
+    ///     coord! { x: 389_250.0, y: 243_750.0 },
+    ///     coord! { x: 390_000.0, y: 244_000.0 },
+    /// );
+    ///
+    /// assert!(gridref.touches_rect(rect));
+    /// assert!(!gridref.within_rect(rect));
+    /// ```
+    pub fn touches_rect(&self, rect: Rect) -> bool {
+        let (sw, ne) = (self.sw(), self.ne());
+
+        sw.x() <= rect.max().x
+            && sw.y() <= rect.max().y
+            && ne.x() >= rect.min().x
+            && ne.y() >= rect.min().y
+    }
+
+    /// Returns whether the point lies within the OSI, expanded
+    /// by the given tolerance in metres on each side. Points on
+    /// the expanded boundary are included.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::Point;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let point = Point::new(389_302.0, 243_750.0);
+    ///
+    /// assert!(!gridref.contains_point_within(point, 0.0));
+    /// assert!(gridref.contains_point_within(point, 5.0));
+    /// ```
+    pub fn contains_point_within(&self, p: Point, tolerance: f64) -> bool {
+        let (sw, ne) = (self.sw(), self.ne());
+
+        p.x() >= sw.x() - tolerance
+            && p.y() >= sw.y() - tolerance
+            && p.x() <= ne.x() + tolerance
+            && p.y() <= ne.y() + tolerance
+    }
+
+    /// Returns the point's position relative to the OSI as `(u, v)`,
+    /// each between 0 and 1, where `(0, 0)` is the South West corner
+    /// and `(1, 1)` the North East. Returns `None` if the point lies
+    /// outside of the OSI.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::Point;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.local_coords(Point::new(389_225.0, 243_775.0)), Some((0.25, 0.75)));
+    /// assert_eq!(gridref.local_coords(Point::new(389_400.0, 243_775.0)), None);
+    /// ```
+    pub fn local_coords(&self, p: Point) -> Option<(f64, f64)> {
+        if !self.contains_point_within(p, 0.0) {
+            return None;
+        }
+
+        let sw = self.sw();
+        let metres = f64::from(self.precision().metres());
+
+        Some(((p.x() - sw.x()) / metres, (p.y() - sw.y()) / metres))
+    }
+
+    /// Returns the boundary shared with another OSI when the two are
+    /// edge-adjacent at the same precision, or `None` otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::{coord, Line};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let east: OSI = "O893437".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     gridref.shared_edge(&east),
+    ///     Some(Line::new(
+    ///         coord! { x: 389_300.0, y: 243_700.0 },
+    ///         coord! { x: 389_300.0, y: 243_800.0 }
+    ///     ))
+    /// );
+    /// ```
+    pub fn shared_edge(&self, other: &Self) -> Option<Line> {
+        if self.precision() != other.precision() {
+            return None;
+        }
+
+        let metres = self.precision().metres();
+        let (east, north) = (self.eastings(), self.northings());
+        let corner = |east: u32, north: u32| coord! { x: east as f64, y: north as f64 };
+
+        let (start, end) = if (other.eastings(), other.northings()) == (east + metres, north) {
+            (
+                corner(east + metres, north),
+                corner(east + metres, north + metres),
+            )
+        } else if (other.eastings() + metres, other.northings()) == (east, north) {
+            (corner(east, north), corner(east, north + metres))
+        } else if (other.eastings(), other.northings()) == (east, north + metres) {
+            (
+                corner(east, north + metres),
+                corner(east + metres, north + metres),
+            )
+        } else if (other.eastings(), other.northings() + metres) == (east, north) {
+            (corner(east, north), corner(east + metres, north))
+        } else {
+            return None;
+        };
+
+        Some(Line::new(start, end))
+    }
+
+    /// Compares the OSI to another in scanline order: by the
+    /// origin's northings from north to south, then its eastings from
+    /// west to east, then by precision from coarsest to finest.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let mut gridrefs: Vec<OSI> = ["O8943", "O9044", "O8944"]
+    ///     .iter()
+    ///     .map(|s| s.parse().unwrap())
+    ///     .collect();
+    /// gridrefs.sort_by(|a, b| a.cmp_scanline(b));
+    ///
+    /// assert_eq!(gridrefs[0].to_string(), "O8944".to_string());
+    /// assert_eq!(gridrefs[1].to_string(), "O9044".to_string());
+    /// assert_eq!(gridrefs[2].to_string(), "O8943".to_string());
+    /// ```
+    pub fn cmp_scanline(&self, other: &Self) -> Ordering {
+        other
+            .northings()
+            .cmp(&self.northings())
+            .then(self.eastings().cmp(&other.eastings()))
+            .then(self.precision().cmp(&other.precision()))
+    }
+
+    /// Returns the finest precision at which the OSI and the other
+    /// grid reference still lie in the same cell, beyond which they
+    /// diverge, or `None` if they do not even share a 100km square.
+    /// Only precisions no finer than either grid reference are compared.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let other: OSI = "O815478".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.divergence_precision(&other), Some(Precision::_10Km));
+    /// ```
+    pub fn divergence_precision(&self, other: &Self) -> Option<Precision> {
+        Precision::ALL
+            .iter()
+            .rev()
+            .filter(|precision| **precision <= self.precision() && **precision <= other.precision())
+            .find(|precision| self.recalculate(**precision) == other.recalculate(**precision))
+            .copied()
+    }
+
+    /// Returns the king-move distance in cells to the other grid
+    /// reference, counting diagonal steps as one, or `None` if
+    /// the two are of different precisions.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let other: OSI = "O9145".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.chebyshev_distance(&other), Some(2));
+    /// ```
+    pub fn chebyshev_distance(&self, other: &Self) -> Option<u32> {
+        let (columns, rows) = self.cell_delta(other)?;
+
+        Some(columns.max(rows))
+    }
+
+    /// Returns the taxicab distance in cells to the other grid
+    /// reference, moving only along rows and columns, or `None`
+    /// if the two are of different precisions.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let other: OSI = "O9145".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.manhattan_distance(&other), Some(4));
+    /// ```
+    pub fn manhattan_distance(&self, other: &Self) -> Option<u32> {
+        let (columns, rows) = self.cell_delta(other)?;
+
+        Some(columns + rows)
+    }
+
+    /// Returns the neighbouring grid reference, including diagonals,
+    /// one step closer to the target. Returns `None` if the OSI is
+    /// the target, if the two are of different precisions, or if the
+    /// step would leave the grid.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let target: OSI = "O9245".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.step_toward(&target).unwrap().to_string(), "O9044".to_string());
+    /// ```
+    pub fn step_toward(&self, target: &Self) -> Option<Self> {
+        if self.precision() != target.precision() || self == target {
+            return None;
+        }
+
+        let (column, row) = self.global_cell_index();
+        let (target_column, target_row) = target.global_cell_index();
+
+        self.offset(
+            i64::from(target_column).cmp(&i64::from(column)) as i64,
+            i64::from(target_row).cmp(&i64::from(row)) as i64,
+        )
+    }
+
+    /// Returns the bearing from the OSI's centre to another's,
+    /// in degrees clockwise from grid north, in the range 0 to 360.
+    ///
+    /// This is a bearing within the flat plane of the grid, and will
+    /// differ from the true or magnetic bearing between the two.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let other: OSI = "O9044".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.grid_bearing_to(&other), 45.0);
+    /// ```
+    pub fn grid_bearing_to(&self, other: &Self) -> f64 {
+        let (from, to) = (self.centre(), other.centre());
+        let bearing = (to.x() - from.x()).atan2(to.y() - from.y()).to_degrees();
+
+        bearing.rem_euclid(360.0)
+    }
+
+    /// Returns the grid reference at the given precision containing the
+    /// point a fraction `t` of the way from the OSI's centre to another's.
+    /// `t` is clamped to the range 0 to 1.
+    ///
+    /// # Errors
+    /// Returns an error if `t` is not a number.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let other: OSI = "O9145".parse().unwrap();
+    /// let midpoint = gridref.interpolate_to(&other, 0.5, Precision::_1Km).unwrap();
+    ///
+    /// assert_eq!(midpoint.to_string(), "O9044".to_string());
+    /// ```
+    pub fn interpolate_to(
+        &self,
+        other: &Self,
+        t: f64,
+        precision: Precision,
+    ) -> Result<Self, Error> {
+        if t.is_nan() {
+            return Err(Error::OutOfBounds);
+        }
+
+        let t = t.clamp(0.0, 1.0);
+        let (from, to) = (self.centre(), other.centre());
+        let eastings = from.x() + (to.x() - from.x()) * t;
+        let northings = from.y() + (to.y() - from.y()) * t;
+
+        Self::new(eastings as u32, northings as u32, precision)
+    }
+
+    /// Returns the grid reference whose centre is
+    /// nearest to the given point, or `None` if
+    /// the slice is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use geo_types::Point;
+    ///
+    /// let gridrefs: Vec<OSI> = ["O892437", "O912437"]
+    ///     .iter()
+    ///     .map(|s| s.parse().unwrap())
+    ///     .collect();
+    ///
+    /// let nearest = OSI::nearest(&gridrefs, Point::new(391_000.0, 243_000.0)).unwrap();
+    /// assert_eq!(nearest.to_string(), "O912437".to_string());
+    /// ```
+    pub fn nearest(refs: &[Self], point: Point) -> Option<&Self> {
+        refs.iter()
+            .min_by(|a, b| distance(&a.centre(), &point).total_cmp(&distance(&b.centre(), &point)))
+    }
+
+    /// Returns the grid references at the given precision whose
+    /// centres lie within the radius, in metres, of the given point.
+    /// They are ordered from south to north, then west to east.
+    ///
+    /// # Errors
+    /// Returns an error if the radius is negative or not finite,
+    /// or if any part of the search area lies outside of the grid.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    /// use geo_types::Point;
+    ///
+    /// let cells = OSI::cells_within_radius(
+    ///     Point::new(389_500.0, 243_500.0),
+    ///     100.0,
+    ///     Precision::_100M,
+    /// ).unwrap();
+    ///
+    /// assert_eq!(cells.len(), 4);
+    /// ```
+    pub fn cells_within_radius(
+        centre: Point,
+        radius: f64,
+        precision: Precision,
+    ) -> Result<Vec<Self>, Error> {
+        if !radius.is_finite() || radius < 0.0 {
+            return Err(Error::OutOfBounds);
+        }
+
+        let (min_x, min_y) = (centre.x() - radius, centre.y() - radius);
+        let (max_x, max_y) = (centre.x() + radius, centre.y() + radius);
+        if min_x < 0.0 || min_y < 0.0 || max_x >= f64::from(WIDTH) || max_y >= f64::from(HEIGHT) {
+            return Err(Error::OutOfBounds);
+        }
+
+        let metres = f64::from(precision.metres());
+        let (first_column, last_column) = ((min_x / metres) as u32, (max_x / metres) as u32);
+        let (first_row, last_row) = ((min_y / metres) as u32, (max_y / metres) as u32);
+
+        let mut cells = vec![];
+        for row in first_row..=last_row {
+            for column in first_column..=last_column {
+                let cell = Self::from_global_cell_index(column, row, precision)?;
+
+                if distance(&cell.centre(), &centre) <= radius {
+                    cells.push(cell);
+                }
+            }
+        }
+
+        Ok(cells)
+    }
+
+    /// Returns the grid references at the given precision whose
+    /// centres lie within any part of the multipolygon, excluding
+    /// holes. They are ordered from south to north, then west to east.
+    /// The multipolygon is assumed to share the grid's coordinate system.
+    ///
+    /// # Errors
+    /// Returns an error if any part of the multipolygon's
+    /// bounding box lies outside of the grid.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    /// use geo_types::{LineString, MultiPolygon, Polygon};
+    ///
+    /// let square = |x: f64, y: f64| {
+    ///     Polygon::new(
+    ///         LineString::from(vec![(x, y), (x + 2_000.0, y), (x + 2_000.0, y + 2_000.0), (x, y + 2_000.0)]),
+    ///         vec![],
+    ///     )
+    /// };
+    /// let area = MultiPolygon::new(vec![square(389_000.0, 243_000.0), square(395_000.0, 243_000.0)]);
+    ///
+    /// let cells = OSI::cells_in_multipolygon(&area, Precision::_1Km).unwrap();
+    /// assert_eq!(cells.len(), 8);
+    /// ```
+    pub fn cells_in_multipolygon(
+        mp: &MultiPolygon,
+        precision: Precision,
+    ) -> Result<Vec<Self>, Error> {
+        let mut coords = mp.iter().flat_map(|poly| poly.exterior().coords());
+        let first = match coords.next() {
+            Some(first) => *first,
+            None => return Ok(vec![]),
+        };
+        let (min, max) = coords.fold((first, first), |(min, max), c| {
+            (
+                coord! { x: min.x.min(c.x), y: min.y.min(c.y) },
+                coord! { x: max.x.max(c.x), y: max.y.max(c.y) },
+            )
+        });
+
+        if min.x < 0.0 || min.y < 0.0 || max.x >= f64::from(WIDTH) || max.y >= f64::from(HEIGHT) {
+            return Err(Error::OutOfBounds);
+        }
+
+        let metres = f64::from(precision.metres());
+        let (first_column, last_column) = ((min.x / metres) as u32, (max.x / metres) as u32);
+        let (first_row, last_row) = ((min.y / metres) as u32, (max.y / metres) as u32);
+
+        let mut cells = vec![];
+        for row in first_row..=last_row {
+            for column in first_column..=last_column {
+                let cell = Self::from_global_cell_index(column, row, precision)?;
+
+                if mp.iter().any(|poly| polygon_contains(poly, &cell.centre())) {
+                    cells.push(cell);
+                }
+            }
+        }
+
+        Ok(cells)
+    }
+
+    /// Returns the grid references at the given precision that the
+    /// outline of a circle passes through, without its interior.
+    /// The radius, in metres, is rounded to a whole number of cells
+    /// around the cell containing the centre, and the outline is
+    /// walked using the midpoint circle algorithm.
+    /// They are ordered from south to north, then west to east.
+    ///
+    /// # Errors
+    /// Returns an error if the radius is negative or not finite,
+    /// or if any part of the circle lies outside of the grid.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    /// use geo_types::Point;
+    ///
+    /// let cells = OSI::cells_on_circle(
+    ///     Point::new(389_500.0, 243_500.0),
+    ///     2_000.0,
+    ///     Precision::_1Km,
+    /// ).unwrap();
+    ///
+    /// assert_eq!(cells.len(), 12);
+    /// assert_eq!(cells[0].to_string(), "O8841".to_string());
+    /// ```
+    pub fn cells_on_circle(
+        centre: Point,
+        radius: f64,
+        precision: Precision,
+    ) -> Result<Vec<Self>, Error> {
+        if !radius.is_finite() || radius < 0.0 {
+            return Err(Error::OutOfBounds);
+        }
+
+        let metres = f64::from(precision.metres());
+        let cells = (radius / metres).round();
+        let (column, row) = ((centre.x() / metres).floor(), (centre.y() / metres).floor());
+        if column - cells < 0.0
+            || row - cells < 0.0
+            || (column + cells + 1.0) * metres > f64::from(WIDTH)
+            || (row + cells + 1.0) * metres > f64::from(HEIGHT)
+        {
+            return Err(Error::OutOfBounds);
+        }
+
+        // Casting is safe here as the circle was checked to lie within the grid.
+        let (column, row) = (column as i64, row as i64);
+        circle_cells(cells as u32)
+            .into_iter()
+            .map(|(columns, rows)| {
+                Self::from_global_cell_index(
+                    (column + columns) as u32,
+                    (row + rows) as u32,
+                    precision,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the distinct grid references at the given precision
+    /// that the line string passes through, in the order they are
+    /// first reached along it.
+    /// The line string is assumed to share the grid's coordinate system.
+    ///
+    /// # Errors
+    /// Returns an error if any part of the line string
+    /// lies outside of the grid.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    /// use geo_types::LineString;
+    ///
+    /// let route = LineString::from(vec![(389_500.0, 243_500.0), (391_500.0, 243_500.0)]);
+    /// let cells: Vec<String> = OSI::cells_along(&route, Precision::_1Km)
+    ///     .unwrap()
+    ///     .iter()
+    ///     .map(|gridref| gridref.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(cells, vec!["O8943", "O9043", "O9143"]);
+    /// ```
+    pub fn cells_along(line: &LineString, precision: Precision) -> Result<Vec<Self>, Error> {
+        let within_grid = |c: &Coord| {
+            (0.0..f64::from(WIDTH)).contains(&c.x) && (0.0..f64::from(HEIGHT)).contains(&c.y)
+        };
+        if !line.coords().all(within_grid) {
+            return Err(Error::OutOfBounds);
+        }
+
+        line_string_cells(line, f64::from(precision.metres()))
+            .into_iter()
+            .map(|(column, row)| Self::from_global_cell_index(column, row, precision))
+            .collect()
+    }
+
+    /// Returns the grid reference at the given precision containing
+    /// the mean of the given references' centres, or `None` if the
+    /// slice is empty or the mean falls outside of the grid.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridrefs: Vec<OSI> = ["O8843", "O9043"]
+    ///     .iter()
+    ///     .map(|s| s.parse().unwrap())
+    ///     .collect();
+    ///
+    /// let centroid = OSI::centroid_of(&gridrefs, Precision::_1Km).unwrap();
+    /// assert_eq!(centroid.to_string(), "O8943".to_string());
+    /// ```
+    pub fn centroid_of(refs: &[Self], precision: Precision) -> Option<Self> {
+        if refs.is_empty() {
+            return None;
+        }
+
+        let count = refs.len() as f64;
+        let (eastings, northings) = refs.iter().fold((0.0, 0.0), |(x, y), gridref| {
+            let centre = gridref.centre();
+            (x + centre.x(), y + centre.y())
+        });
+
+        Self::new(
+            (eastings / count) as u32,
+            (northings / count) as u32,
+            precision,
+        )
+        .ok()
+    }
+
+    /// Returns the grid references within the OSI at each finer
+    /// precision, down to and including the given precision.
+    /// Each level is ordered from south to north, then west to east.
+    /// Precisions that do not evenly divide the OSI are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let pyramid = gridref.pyramid(Precision::_10M);
+    ///
+    /// assert_eq!(pyramid[0].len(), 100);
+    /// assert_eq!(pyramid[1].len(), 10_000);
+    /// ```
+    pub fn pyramid(&self, down_to: Precision) -> Vec<Vec<Self>> {
+        Precision::ALL
+            .iter()
+            .filter(|precision| **precision <= down_to)
+            .filter(|precision| self.precision().ratio_to(precision).is_some())
+            .map(|precision| self.children(*precision))
+            .collect()
+    }
+
+    /// Returns the grid reference at the mirror position of the OSI
+    /// through the centre of its containing cell at the given coarser
+    /// precision, such as the opposite corner. Returns `None` if the
+    /// precision is not coarser than the OSI's.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref: OSI = "O890430".parse().unwrap();
+    /// let reflected = gridref.reflect_in_parent(Precision::_1Km).unwrap();
+    ///
+    /// assert_eq!(reflected.to_string(), "O899439".to_string());
+    /// ```
+    pub fn reflect_in_parent(&self, parent: Precision) -> Option<Self> {
+        parent.ratio_to(&self.precision())?;
+
+        let parent = self.recalculate(parent);
+        let metres = self.precision().metres();
+        let span = parent.precision().metres() - metres;
+        let eastings = parent.eastings() + span - (self.eastings() - parent.eastings());
+        let northings = parent.northings() + span - (self.northings() - parent.northings());
+
+        // Unwrapping here as the reflection lies within the parent.
+        Some(Self::new(eastings, northings, self.precision()).unwrap())
+    }
+
+    /// Returns the grid references within the OSI at the given
+    /// finer precision, except those lying within `exclude`.
+    /// They are ordered from south to north, then west to east.
+    /// Returns an empty vector if the precision is not finer,
+    /// or does not evenly divide the cell.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let exclude: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.children_excluding(&exclude, Precision::_100M).len(), 99);
+    /// ```
+    pub fn children_excluding(&self, exclude: &Self, precision: Precision) -> Vec<Self> {
+        self.children(precision)
+            .into_iter()
+            .filter(|child| {
+                exclude.precision() > precision
+                    || child.recalculate(exclude.precision()) != *exclude
+            })
+            .collect()
+    }
+
+    /// Splits the OSI into its four equal quadrants, each with
+    /// half the edge length, ordered as 'South West', 'North West',
+    /// 'North East', 'South East'.
+    ///
+    /// Returns `None` if half the edge length is not a supported precision.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    ///
+    /// assert!(gridref.quadrants().is_none());
+    /// ```
+    pub fn quadrants(&self) -> Option<[Self; 4]> {
+        // Odd edge lengths can not be halved, and fall through to None.
+        let half = self.point.precision().metres() / 2;
+        let precision = Precision::from_metres(half)?;
+        let eastings = self.point.eastings().inner();
+        let northings = self.point.northings().inner();
+
+        let quadrant = |east: u32, north: u32| {
+            // Unwrapping here as quadrants lie within the current square.
+            Self::new(eastings + east, northings + north, precision).unwrap()
+        };
+
+        Some([
+            quadrant(0, 0),
+            quadrant(0, half),
+            quadrant(half, half),
+            quadrant(half, 0),
+        ])
+    }
+
+    /// Returns the eastings and northings of the OSI's
+    /// origin within its containing 100km square, in metres.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.offset_in_square(), (89_200, 43_700));
+    /// ```
+    pub fn offset_in_square(&self) -> (u32, u32) {
+        (
+            self.point.eastings().inner() % _100KM,
+            self.point.northings().inner() % _100KM,
+        )
+    }
+
+    /// Returns the 100km squares sharing an edge with the OSI's own
+    /// 100km square, ordered as North, East, South, West.
+    /// Any that fall outside of the grid are `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let [north, _, _, _] = gridref.neighbouring_squares();
+    ///
+    /// assert_eq!(north.unwrap().to_string(), "J".to_string());
+    /// ```
+    pub fn neighbouring_squares(&self) -> [Option<Self>; 4] {
+        let square = self.recalculate(Precision::_100Km);
+
+        [(0, 1), (1, 0), (0, -1), (-1, 0)].map(|(columns, rows)| square.offset(columns, rows))
+    }
+
+    /// Returns the OSI's column and row indices into a global
+    /// raster of cells at its precision, counted from the grid's origin.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.global_cell_index(), (389, 243));
+    /// ```
+    pub fn global_cell_index(&self) -> (u32, u32) {
+        let metres = self.precision().metres();
+
+        (self.eastings() / metres, self.northings() / metres)
+    }
+
+    /// Returns the grid reference at the same precision diametrically
+    /// opposite the OSI, reflecting its centre through the centre of
+    /// the grid's extent. Returns `None` if the result lies outside
+    /// the valid grid.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "V0000".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.antipode().unwrap().to_string(), "E9999".to_string());
+    /// ```
+    pub fn antipode(&self) -> Option<Self> {
+        let metres = self.precision().metres();
+        let eastings = WIDTH - metres - self.eastings();
+        let northings = HEIGHT - metres - self.northings();
+
+        Self::new(eastings, northings, self.precision()).ok()
+    }
+
+    /// Returns the parity of the OSI's column plus row on the
+    /// whole grid at its precision, for shading a checkerboard.
+    /// Cells sharing an edge always have opposite parities.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let east: OSI = "O9043".parse().unwrap();
+    ///
+    /// assert_ne!(gridref.checker_parity(), east.checker_parity());
+    /// ```
+    pub fn checker_parity(&self) -> bool {
+        let (column, row) = self.global_cell_index();
+
+        !(column + row).is_multiple_of(2)
+    }
+
+    /// Returns the kilometre indices of the 1km cell containing the
+    /// OSI's south west corner, as eastings and northings from
+    /// the grid's origin in kilometres.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.km_index(), (389, 243));
+    /// ```
+    pub fn km_index(&self) -> (u32, u32) {
+        (self.eastings() / _1KM, self.northings() / _1KM)
+    }
+
+    /// Returns the grid references sharing an edge with the OSI,
+    /// at the same precision, ordered as North, East, South, West.
+    /// Any that fall outside of the grid are omitted.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let neighbours: Vec<String> = gridref
+    ///     .edge_neighbours()
+    ///     .iter()
+    ///     .map(|gridref| gridref.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(neighbours, vec!["O8944", "O9043", "O8942", "O8843"]);
+    /// ```
+    pub fn edge_neighbours(&self) -> Vec<Self> {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .into_iter()
+            .filter_map(|(columns, rows)| self.offset(columns, rows))
+            .collect()
+    }
+
+    /// Returns the OSI followed by the grid references the given
+    /// number of cells away to the North, East, South, and West,
+    /// at the same precision. Any that fall outside of the grid
+    /// are omitted, and a radius of zero returns only the OSI.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let plus: Vec<String> = gridref
+    ///     .plus(2)
+    ///     .iter()
+    ///     .map(|gridref| gridref.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(plus, vec!["O8943", "O8945", "O9143", "O8941", "O8743"]);
+    /// ```
+    pub fn plus(&self, radius: u32) -> Vec<Self> {
+        let radius = i64::from(radius);
+        let arms = if radius == 0 {
+            vec![]
+        } else {
+            vec![(0, radius), (radius, 0), (0, -radius), (-radius, 0)]
+        };
+
+        std::iter::once(self.clone())
+            .chain(
+                arms.into_iter()
+                    .filter_map(|(columns, rows)| self.offset(columns, rows)),
+            )
+            .collect()
+    }
+
+    /// Returns an iterator over grid references at the same precision,
+    /// in an outward clockwise spiral starting from the OSI and
+    /// heading east. Any that fall outside of the grid are skipped,
+    /// and the iterator ends once the spiral has passed the whole grid.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O8943".parse().unwrap();
+    /// let spiral: Vec<String> = gridref
+    ///     .spiral()
+    ///     .take(4)
+    ///     .map(|gridref| gridref.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(spiral, vec!["O8943", "O9043", "O9042", "O8942"]);
+    /// ```
+    pub fn spiral(&self) -> impl Iterator<Item = Self> {
+        let origin = self.clone();
+        let limit = i64::from(WIDTH.max(HEIGHT) / self.precision().metres());
+
+        // Legs of the spiral turn clockwise and grow every second turn.
+        let steps = (0..).flat_map(|leg: i64| {
+            let direction = [(1, 0), (0, -1), (-1, 0), (0, 1)][(leg % 4) as usize];
+
+            std::iter::repeat_n(direction, (leg / 2 + 1) as usize)
+        });
+
+        std::iter::once((0, 0))
+            .chain(steps.scan((0, 0), |position: &mut (i64, i64), step| {
+                *position = (position.0 + step.0, position.1 + step.1);
+                Some(*position)
+            }))
+            .take_while(move |(columns, rows)| columns.abs().max(rows.abs()) <= limit)
+            .filter_map(move |(columns, rows)| origin.offset(columns, rows))
+    }
+
+    /// Returns the width and height of the OSI's cell in metres.
+    /// Cells are always square, so both are equal to the precision.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.dimensions(), (100, 100));
+    /// ```
+    pub fn dimensions(&self) -> (u32, u32) {
+        let metres = self.precision().metres();
+
+        (metres, metres)
+    }
+
+    /// Returns the national grid the OSI belongs to.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{GridSystem, OSI};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.grid_system(), GridSystem::Osi);
+    /// ```
+    pub fn grid_system(&self) -> GridSystem {
+        GridSystem::Osi
+    }
+
+    /// Returns the OSI's precision.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::{OSI, Precision};
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.precision(), Precision::_100M);
+    /// ```
+    pub fn precision(&self) -> Precision {
+        self.point.precision()
+    }
+
+    /// Writes the OSI's string representation into the given writer,
+    /// allowing a single buffer to be reused across many grid references.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let mut buffer = String::new();
+    /// gridref.write_to(&mut buffer).unwrap();
+    ///
+    /// assert_eq!(buffer, "O892437".to_string());
+    /// ```
+    pub fn write_to<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
+        write!(w, "{}", self.point)
+    }
+
+    /// Writes the OSI's string representation into the given
+    /// byte buffer as ASCII, returning the number of bytes written.
+    /// Nothing is allocated, so the buffer can live on the stack.
+    ///
+    /// # Errors
+    /// Returns an error if the buffer is shorter than
+    /// [`OSI::display_len`].
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let mut buffer = [0; 16];
+    /// let len = gridref.format_into(&mut buffer).unwrap();
+    ///
+    /// assert_eq!(&buffer[..len], b"O892437");
+    /// ```
+    pub fn format_into(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        let mut writer = SliceWriter::new(buf);
+
+        self.write_to(&mut writer)
+            .map_err(|_| Error::BufferTooSmall(self.display_len()))?;
+
+        Ok(writer.len())
+    }
+
+    /// Parses a grid reference from ASCII bytes, such as those
+    /// written by [`OSI::format_into`].
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not valid UTF-8,
+    /// or can not be parsed as a grid reference.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref = OSI::parse_ascii(b"O892437").unwrap();
+    ///
+    /// assert_eq!(gridref.to_string(), "O892437".to_string());
+    /// ```
+    pub fn parse_ascii(bytes: &[u8]) -> Result<Self, Error> {
+        std::str::from_utf8(bytes)
+            .map_err(|e| Error::ParseError(format!("{e}")))?
+            .parse()
+    }
+
+    /// Returns the exact length of the OSI's string representation,
+    /// which can be used to pre-size a buffer before writing it.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    /// use std::fmt::Write;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    /// let mut string = String::with_capacity(gridref.display_len());
+    /// write!(string, "{gridref}").unwrap();
+    ///
+    /// assert_eq!(string.len(), gridref.display_len());
+    /// ```
+    pub fn display_len(&self) -> usize {
+        self.point.display_len()
+    }
+
+    /// Returns the number of figures in the OSI's numeric part,
+    /// such as 6 for a 100m grid reference.
+    ///
+    /// A 2km tetrad grid reference carries 2 figures,
+    /// followed by the tetrad letter, and a 5km pentad
+    /// grid reference 2 figures, followed by its quadrant.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.figures(), 6);
+    /// ```
+    pub fn figures(&self) -> usize {
+        self.point.precision().digits()
+    }
+
+    // Returns the eastings of the OSI's origin.
+    fn eastings(&self) -> u32 {
+        self.point.eastings().inner()
+    }
+
+    // Returns the northings of the OSI's origin.
+    fn northings(&self) -> u32 {
+        self.point.northings().inner()
+    }
+
+    /// Returns the OSI in the form used when teaching grid
+    /// references, with the square's letters spelled as numbers.
+    ///
+    /// The format is `EE,NN figures`, where `EE` and `NN` are the
+    /// two-digit eastings and northings of the 100km square's origin,
+    /// in units of 100km, and `figures` are the grid reference's
+    /// figures within that square, unchanged. A 100km grid reference
+    /// has no figures, so is formatted as `EE,NN` alone.
+    ///
+    /// # Example
+    /// ```
+    /// use gridish::OSI;
+    ///
+    /// let gridref: OSI = "O892437".parse().unwrap();
+    ///
+    /// assert_eq!(gridref.to_teaching_form(), "03,02 892437".to_string());
+    /// ```
+    pub fn to_teaching_form(&self) -> String {
+        let square = format!(
+            "{:02},{:02}",
+            self.eastings() / _100KM,
+            self.northings() / _100KM
+        );
+        // The figures follow the single square letter.
+        let gridref = self.to_string();
+        let figures = &gridref[1..];
+
+        if figures.is_empty() {
+            square
+        } else {
+            format!("{square} {figures}")
+        }
+    }
+
+    // Returns the absolute number of columns and rows between
+    // the two grid references, if they share a precision.
+    fn cell_delta(&self, other: &Self) -> Option<(u32, u32)> {
+        if self.precision() != other.precision() {
+            return None;
+        }
+
+        let (column, row) = self.global_cell_index();
+        let (other_column, other_row) = other.global_cell_index();
+
+        Some((column.abs_diff(other_column), row.abs_diff(other_row)))
+    }
+
+    // Returns the grid references at the given finer precision within
+    // the OSI, ordered from south to north, then west to east.
+    // Empty if the precision is not finer, or does not evenly tile
+    // the OSI, such as a 2km tetrad within a 5km pentad.
+    fn children(&self, precision: Precision) -> Vec<Self> {
+        let metres = precision.metres();
+        let Some(count) = self.precision().ratio_to(&precision) else {
+            return vec![];
+        };
+
+        (0..count)
+            .flat_map(|row| (0..count).map(move |column| (column, row)))
+            .map(|(column, row)| {
+                // Unwrapping here as children lie within the current square.
+                Self::new(
+                    self.eastings() + column * metres,
+                    self.northings() + row * metres,
+                    precision,
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    // Returns the grid reference offset by the given number of
+    // cells at the current precision, if it lies within the grid.
+    fn offset(&self, columns: i64, rows: i64) -> Option<Self> {
+        let metres = i64::from(self.point.precision().metres());
+        let eastings = i64::from(self.point.eastings().inner()) + (columns * metres);
+        let northings = i64::from(self.point.northings().inner()) + (rows * metres);
+
+        Self::new(
+            eastings.try_into().ok()?,
+            northings.try_into().ok()?,
+            self.point.precision(),
+        )
+        .ok()
+    }
+}
+
+impl FromStr for OSI {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let string: String = trim_string(s);
+        let point: GridPoint = string.parse()?;
+
+        Ok(Self { point })
+    }
+}
+
+/// Offsets the grid reference by a number of cells, as (columns, rows),
+/// at its current precision. Positive columns move east, and positive
+/// rows move north.
+///
+/// Returns an error rather than wrapping or clamping
+/// if the result falls outside of the grid.
+///
+/// # Example
+/// ```
+/// use gridish::OSI;
+///
+/// let gridref: OSI = "O8943".parse().unwrap();
+///
+/// assert_eq!((gridref.clone() + (1, 0)).unwrap().to_string(), "O9043".to_string());
+/// assert_eq!((gridref + (-1, -1)).unwrap().to_string(), "O8842".to_string());
+/// ```
+impl Add<(i32, i32)> for OSI {
+    type Output = Result<Self, Error>;
+
+    fn add(self, (columns, rows): (i32, i32)) -> Self::Output {
+        self.offset(i64::from(columns), i64::from(rows))
+            .ok_or(Error::OutOfBounds)
+    }
+}
+
+impl TryFrom<&str> for OSI {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for OSI {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.as_str().parse()
+    }
+}
+
+impl Display for OSI {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_to(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Error, Precision, OSI};
+    use geo_types::{coord, Line, LineString, Point, Polygon, Rect};
+
+    #[test]
+    fn coordinates_are_correct() {
+        let osi = OSI::new(0, 0, crate::Precision::_100M).unwrap();
+        let sw = osi.sw();
+        let nw = osi.nw();
+        let ne = osi.ne();
+        let se = osi.se();
+
+        assert_eq!(sw, Point::new(0.0, 0.0));
+        assert_eq!(nw, Point::new(0.0, 100.0));
+        assert_eq!(ne, Point::new(100.0, 100.0));
+        assert_eq!(se, Point::new(100.0, 0.0));
+        assert_eq!(osi.centre(), Point::new(50.0, 50.0));
+        assert_eq!(osi.coord_bounds(), (sw.into(), ne.into()));
+        assert_eq!(
+            osi.perimeter(),
+            Polygon::new(LineString::from(vec![sw, nw, ne, se]), vec![])
+        )
+    }
+
+    #[test]
+    fn calculates_coverage_fraction() {
+        let gridref: OSI = "O892437".parse().unwrap();
+        let rect = |x_max: f64| {
+            Polygon::new(
+                LineString::from(vec![
+                    (389_000.0, 243_000.0),
+                    (389_000.0, 244_000.0),
+                    (x_max, 244_000.0),
+                    (x_max, 243_000.0),
+                ]),
+                vec![],
+            )
+        };
+
+        assert!((gridref.coverage_fraction(&rect(389_250.0)) - 0.5).abs() < 1e-9);
+        assert_eq!(gridref.coverage_fraction(&rect(390_000.0)), 1.0);
+        assert_eq!(gridref.coverage_fraction(&rect(389_100.0)), 0.0);
+    }
+
+    #[test]
+    fn contains_point_within_tolerance() {
+        let gridref: OSI = "O892437".parse().unwrap();
+        let inside = Point::new(389_250.0, 243_750.0);
+        let on_edge = Point::new(389_300.0, 243_750.0);
+        let outside = Point::new(389_250.0, 243_698.0);
+
+        assert!(gridref.contains_point_within(inside, 0.0));
+        assert!(gridref.contains_point_within(on_edge, 0.0));
+        assert!(!gridref.contains_point_within(outside, 0.0));
+        assert!(!gridref.contains_point_within(outside, 1.0));
+        assert!(gridref.contains_point_within(outside, 2.0));
+    }
+
+    #[test]
+    fn calculates_local_coords() {
+        let gridref: OSI = "O892437".parse().unwrap();
+
+        assert_eq!(gridref.local_coords(gridref.centre()), Some((0.5, 0.5)));
+        assert_eq!(gridref.local_coords(gridref.sw()), Some((0.0, 0.0)));
+        assert_eq!(gridref.local_coords(gridref.ne()), Some((1.0, 1.0)));
+        assert_eq!(gridref.local_coords(Point::new(389_250.0, 243_699.0)), None);
+    }
+
+    #[test]
+    fn centre_within_polygon() {
+        let area = Polygon::new(
+            LineString::from(vec![
+                (389_000.0, 243_000.0),
+                (389_000.0, 244_000.0),
+                (390_000.0, 244_000.0),
+                (390_000.0, 243_000.0),
+            ]),
+            vec![],
+        );
+
+        let inside: OSI = "O892437".parse().unwrap();
+        let outside: OSI = "O902437".parse().unwrap();
+
+        assert!(inside.centre_within(&area));
+        assert!(!outside.centre_within(&area));
+    }
+
+    #[test]
+    fn round_trips_bytes() {
+        let precisions = [
+            Precision::_100Km,
+            Precision::_10Km,
+            Precision::_1Km,
+            Precision::_100M,
+            Precision::_10M,
+            Precision::_1M,
+        ];
+        let coordinates = [(0, 0), (389_291, 243_762), (499_999, 499_999)];
+
+        for precision in precisions {
+            for (eastings, northings) in coordinates {
+                let gridref = OSI::new(eastings, northings, precision).unwrap();
+
+                assert_eq!(OSI::from_bytes(&gridref.to_bytes()), Ok(gridref));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_bytes() {
+        let bytes: OSI = "O892437".parse().unwrap();
+        let bytes = bytes.to_bytes();
+
+        let mut invalid = bytes;
+        invalid[4..8].copy_from_slice(&500_000_u32.to_be_bytes());
+        assert_eq!(OSI::from_bytes(&invalid), Err(Error::OutOfBounds));
+
+        let mut invalid = bytes;
+        invalid[8] = 255;
+        assert_eq!(
+            OSI::from_bytes(&invalid),
+            Err(Error::InvalidPrecision(
+                "255 is not a valid precision code.".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn normalises_centre() {
+        let gridref: OSI = "V0000".parse().unwrap();
+        let (x, y) = gridref.normalised_centre();
+        assert!(x > 0.0 && x < 0.002);
+        assert!(y > 0.0 && y < 0.002);
+
+        let gridref: OSI = "E9999".parse().unwrap();
+        let (x, y) = gridref.normalised_centre();
+        assert!(x > 0.998 && x < 1.0);
+        assert!(y > 0.998 && y < 1.0);
+    }
+
+    #[test]
+    fn aligns_precisions() {
+        let gridref: OSI = "O892437".parse().unwrap();
+        let other: OSI = "O9043".parse().unwrap();
+
+        let (aligned, aligned_other) = gridref.align_precision_with(&other);
+        assert_eq!(aligned.to_string(), "O8943");
+        assert_eq!(aligned_other.to_string(), "O9043");
+
+        let (aligned_other, aligned) = other.align_precision_with(&gridref);
+        assert_eq!(aligned.to_string(), "O8943");
+        assert_eq!(aligned_other.to_string(), "O9043");
+    }
+
+    #[test]
+    fn compares_with_rect() {
+        let gridref: OSI = "O892437".parse().unwrap();
+
+        // Fully contained.
+        let rect = Rect::new(
+            coord! { x: 389_000.0, y: 243_000.0 },
+            coord! { x: 390_000.0, y: 244_000.0 },
+        );
+        assert!(gridref.within_rect(rect));
+        assert!(gridref.touches_rect(rect));
+
+        // Partially overlapping.
+        let rect = Rect::new(
+            coord! { x: 389_250.0, y: 243_000.0 },
+            coord! { x: 390_000.0, y: 244_000.0 },
+        );
+        assert!(!gridref.within_rect(rect));
+        assert!(gridref.touches_rect(rect));
+
+        // Disjoint.
+        let rect = Rect::new(
+            coord! { x: 389_400.0, y: 243_000.0 },
+            coord! { x: 390_000.0, y: 244_000.0 },
+        );
+        assert!(!gridref.within_rect(rect));
+        assert!(!gridref.touches_rect(rect));
+    }
+
+    #[test]
+    fn finds_shared_edge() {
+        let gridref: OSI = "O892437".parse().unwrap();
+        let east: OSI = "O893437".parse().unwrap();
+        let north: OSI = "O892438".parse().unwrap();
+        let diagonal: OSI = "O893438".parse().unwrap();
+        let coarser: OSI = "O8943".parse().unwrap();
+
+        let edge = Line::new(
+            coord! { x: 389_300.0, y: 243_700.0 },
+            coord! { x: 389_300.0, y: 243_800.0 },
+        );
+        assert_eq!(gridref.shared_edge(&east), Some(edge));
+        assert_eq!(east.shared_edge(&gridref), Some(edge));
+
+        let edge = Line::new(
+            coord! { x: 389_200.0, y: 243_800.0 },
+            coord! { x: 389_300.0, y: 243_800.0 },
+        );
+        assert_eq!(gridref.shared_edge(&north), Some(edge));
+        assert_eq!(north.shared_edge(&gridref), Some(edge));
+
+        assert_eq!(gridref.shared_edge(&diagonal), None);
+        assert_eq!(gridref.shared_edge(&coarser), None);
+        assert_eq!(gridref.shared_edge(&gridref), None);
+    }
+
+    #[test]
+    fn sorts_in_scanline_order() {
+        let mut gridrefs: Vec<OSI> = ["O8943", "O", "O9044", "O8842", "O8944", "O84"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        gridrefs.sort_by(|a, b| a.cmp_scanline(b));
+
+        let gridrefs: Vec<String> = gridrefs.iter().map(|gridref| gridref.to_string()).collect();
+        assert_eq!(
+            gridrefs,
+            vec!["O8944", "O9044", "O8943", "O8842", "O84", "O"]
+        );
+    }
+
+    #[test]
+    fn calculates_grid_bearing() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let bearing = |other: &str| gridref.grid_bearing_to(&other.parse().unwrap());
+
+        assert_eq!(bearing("O8944"), 0.0);
+        assert_eq!(bearing("O9043"), 90.0);
+        assert_eq!(bearing("O8942"), 180.0);
+        assert_eq!(bearing("O8843"), 270.0);
+        assert_eq!(bearing("O8844"), 315.0);
+        assert_eq!(bearing("O8943"), 0.0);
+    }
+
+    #[test]
+    fn interpolates_between_gridrefs() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let other: OSI = "O9145".parse().unwrap();
+        let interpolate = |t: f64, precision: Precision| {
+            gridref
+                .interpolate_to(&other, t, precision)
+                .map(|gridref| gridref.to_string())
+        };
+
+        assert_eq!(interpolate(0.5, Precision::_1Km), Ok("O9044".to_string()));
+        assert_eq!(
+            interpolate(0.25, Precision::_100M),
+            Ok("O900440".to_string())
+        );
+        assert_eq!(interpolate(0.0, Precision::_1Km), Ok("O8943".to_string()));
+        assert_eq!(interpolate(1.0, Precision::_1Km), Ok("O9145".to_string()));
+
+        // Clamps out of range fractions.
+        assert_eq!(interpolate(-1.0, Precision::_1Km), Ok("O8943".to_string()));
+        assert_eq!(interpolate(2.0, Precision::_1Km), Ok("O9145".to_string()));
+
+        assert_eq!(
+            interpolate(f64::NAN, Precision::_1Km),
+            Err(Error::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn finds_nearest() {
+        let gridrefs: Vec<OSI> = ["O892437", "O902437", "O912437"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        let nearest = OSI::nearest(&gridrefs, Point::new(390_200.0, 243_900.0));
+        assert_eq!(nearest, Some(&gridrefs[1]));
+
+        assert_eq!(OSI::nearest(&[], Point::new(390_200.0, 243_900.0)), None);
+    }
+
+    #[test]
+    fn finds_edge_neighbours() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let neighbours: Vec<String> = gridref
+            .edge_neighbours()
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+        assert_eq!(neighbours, vec!["O8944", "O9043", "O8942", "O8843"]);
+
+        // Drops neighbours beyond the grid's south west corner.
+        let gridref: OSI = "V0000".parse().unwrap();
+        let neighbours: Vec<String> = gridref
+            .edge_neighbours()
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+        assert_eq!(neighbours, vec!["V0001", "V0100"]);
+    }
+
+    #[test]
+    fn finds_neighbouring_squares() {
+        let squares = |gridref: &str| -> Vec<Option<String>> {
+            let gridref: OSI = gridref.parse().unwrap();
+
+            gridref
+                .neighbouring_squares()
+                .iter()
+                .map(|square| square.as_ref().map(|square| square.to_string()))
+                .collect()
+        };
+
+        let expected: Vec<Option<String>> = ["J", "P", "T", "N"]
+            .iter()
+            .map(|s| Some(s.to_string()))
+            .collect();
+        assert_eq!(squares("O892437"), expected);
+
+        let expected: Vec<Option<String>> = [Some("Q"), Some("W"), None, None]
+            .iter()
+            .map(|s| s.map(|s| s.to_string()))
+            .collect();
+        assert_eq!(squares("V0000"), expected);
+    }
+
+    #[test]
+    fn adds_cell_offsets() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let add =
+            |offset: (i32, i32)| (gridref.clone() + offset).map(|gridref| gridref.to_string());
+
+        assert_eq!(add((0, 0)), Ok("O8943".to_string()));
+        assert_eq!(add((0, 1)), Ok("O8944".to_string()));
+        assert_eq!(add((1, 0)), Ok("O9043".to_string()));
+        assert_eq!(add((0, -1)), Ok("O8942".to_string()));
+        assert_eq!(add((-1, 0)), Ok("O8843".to_string()));
+        assert_eq!(add((20, -50)), Ok("U0993".to_string()));
+
+        assert_eq!(add((i32::MIN, 0)), Err(Error::OutOfBounds));
+        assert_eq!(add((0, i32::MAX)), Err(Error::OutOfBounds));
+    }
+
+    #[test]
+    fn spirals_clockwise() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let spiral: Vec<String> = gridref
+            .spiral()
+            .take(10)
+            .map(|gridref| gridref.to_string())
+            .collect();
+
+        assert_eq!(
+            spiral,
+            vec![
+                "O8943", "O9043", "O9042", "O8942", "O8842", "O8843", "O8844", "O8944", "O9044",
+                "O9144"
+            ]
+        );
+    }
+
+    #[test]
+    fn spiral_ends_beyond_grid() {
+        let gridref: OSI = "V".parse().unwrap();
+
+        assert_eq!(gridref.spiral().count(), 25);
+    }
+
+    #[test]
+    fn round_trips_global_cell_index() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let (column, row) = gridref.global_cell_index();
+
+        assert_eq!((column, row), (389, 243));
+        assert_eq!(
+            OSI::from_global_cell_index(column, row, Precision::_1Km),
+            Ok(gridref)
+        );
+
+        assert_eq!(
+            OSI::from_global_cell_index(u32::MAX, 0, Precision::_1Km),
+            Err(Error::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn writes_to_reused_buffer() {
+        let gridrefs: Vec<OSI> = ["O892437", "S2468"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let mut buffer = String::new();
+
+        for gridref in &gridrefs {
+            gridref.write_to(&mut buffer).unwrap();
+        }
+        assert_eq!(buffer, "O892437S2468");
+
+        buffer.clear();
+        gridrefs[1].write_to(&mut buffer).unwrap();
+        assert_eq!(buffer, "S2468");
+    }
+
+    #[test]
+    fn counts_figures() {
+        for (gridref, figures) in [
+            ("O", 0),
+            ("O84", 2),
+            ("O8943", 4),
+            ("O892437", 6),
+            ("O89294376", 8),
+            ("O8929143762", 10),
+        ] {
+            let gridref: OSI = gridref.parse().unwrap();
+
+            assert_eq!(gridref.figures(), figures);
+        }
+    }
+
+    #[test]
+    fn finds_cells_within_radius() {
+        let centre = Point::new(389_500.0, 243_500.0);
+        let to_strings = |cells: Vec<OSI>| -> Vec<String> {
+            cells.iter().map(|cell| cell.to_string()).collect()
+        };
+
+        let cells = OSI::cells_within_radius(centre, 150.0, Precision::_100M).unwrap();
+        assert_eq!(
+            to_strings(cells),
+            vec!["O894434", "O895434", "O894435", "O895435"]
+        );
+
+        let cells = OSI::cells_within_radius(centre, 160.0, Precision::_100M).unwrap();
+        assert_eq!(cells.len(), 12);
+        assert!(cells
+            .iter()
+            .all(|cell| cell.centre().x() != 389_350.0 || cell.centre().y() != 243_350.0));
+
+        // The point is exactly the centre of a 1km cell.
+        let cells = OSI::cells_within_radius(centre, 0.0, Precision::_1Km).unwrap();
+        assert_eq!(to_strings(cells), vec!["O8943"]);
+    }
+
+    #[test]
+    fn rejects_radius_outside_grid() {
+        let corner = Point::new(50.0, 50.0);
+
+        assert_eq!(
+            OSI::cells_within_radius(corner, 100.0, Precision::_100M),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(
+            OSI::cells_within_radius(corner, -1.0, Precision::_100M),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(
+            OSI::cells_within_radius(corner, f64::NAN, Precision::_100M),
+            Err(Error::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn finds_cells_along_route() {
+        let route = LineString::from(vec![
+            (389_050.0, 243_050.0),
+            (389_350.0, 243_050.0),
+            (389_350.0, 243_250.0),
+        ]);
+        let cells: Vec<String> = OSI::cells_along(&route, Precision::_100M)
+            .unwrap()
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+
+        assert_eq!(
+            cells,
+            vec!["O890430", "O891430", "O892430", "O893430", "O893431", "O893432"]
+        );
+
+        let outside = LineString::from(vec![(389_050.0, 243_050.0), (-1.0, 243_050.0)]);
+        assert_eq!(
+            OSI::cells_along(&outside, Precision::_100M),
+            Err(Error::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn finds_centroid() {
+        let gridrefs: Vec<OSI> = ["O8843", "O9043", "O8942", "O8944"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        let centroid = OSI::centroid_of(&gridrefs, Precision::_1Km).unwrap();
+        assert_eq!(centroid.to_string(), "O8943");
+        let centroid = OSI::centroid_of(&gridrefs, Precision::_100M).unwrap();
+        assert_eq!(centroid.to_string(), "O895435");
+
+        assert_eq!(OSI::centroid_of(&[], Precision::_1Km), None);
+    }
+
+    #[test]
+    fn calculates_floor_residual() {
+        assert_eq!(
+            OSI::floor_residual(389_290, 243_760, Precision::_100M),
+            (90, 60)
+        );
+        assert_eq!(
+            OSI::floor_residual(389_290, 243_760, Precision::_10Km),
+            (9_290, 3_760)
+        );
+        assert_eq!(
+            OSI::floor_residual(389_290, 243_760, Precision::_1M),
+            (0, 0)
+        );
+
+        let gridref = OSI::new(389_290, 243_760, Precision::_100M).unwrap();
+        assert_eq!(gridref.sw(), Point::new(389_200.0, 243_700.0));
+    }
+
+    #[test]
+    fn reflects_in_parent() {
+        let corner: OSI = "O890430".parse().unwrap();
+        let reflected = corner.reflect_in_parent(Precision::_1Km).unwrap();
+        assert_eq!(reflected.to_string(), "O899439");
+        assert_eq!(reflected.reflect_in_parent(Precision::_1Km), Some(corner));
+
+        let gridref: OSI = "O892437".parse().unwrap();
+        let reflected = gridref.reflect_in_parent(Precision::_10Km).unwrap();
+        assert_eq!(reflected.to_string(), "O807462");
+
+        assert_eq!(gridref.reflect_in_parent(Precision::_100M), None);
+        assert_eq!(gridref.reflect_in_parent(Precision::_10M), None);
+    }
+
+    #[test]
+    fn excludes_children() {
+        let gridref: OSI = "O8943".parse().unwrap();
+
+        let exclude: OSI = "O892437".parse().unwrap();
+        let children = gridref.children_excluding(&exclude, Precision::_100M);
+        assert_eq!(children.len(), 99);
+        assert!(!children.contains(&exclude));
+
+        let children = gridref.children_excluding(&exclude, Precision::_10M);
+        assert_eq!(children.len(), 9_900);
+
+        // A finer exclusion does not contain any whole children.
+        let exclude: OSI = "O89204370".parse().unwrap();
+        assert_eq!(
+            gridref.children_excluding(&exclude, Precision::_100M).len(),
+            100
+        );
+
+        // Excluding the whole cell, or a cell elsewhere.
+        assert!(gridref
+            .children_excluding(&gridref, Precision::_100M)
+            .is_empty());
+        let elsewhere: OSI = "O9043".parse().unwrap();
+        assert_eq!(
+            gridref
+                .children_excluding(&elsewhere, Precision::_100M)
+                .len(),
+            100
+        );
+
+        assert!(gridref
+            .children_excluding(&exclude, Precision::_1Km)
+            .is_empty());
+    }
+
+    #[test]
+    fn lists_ancestry() {
+        let gridref: OSI = "O892437".parse().unwrap();
+        let ancestry: Vec<String> = gridref
+            .ancestry()
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+
+        assert_eq!(ancestry.first().unwrap(), "O892437");
+        assert!(ancestry.contains(&"O8943".to_string()));
+        assert!(ancestry.contains(&"O84".to_string()));
+        assert_eq!(ancestry.last().unwrap(), "O");
+
+        let gridref: OSI = "O".parse().unwrap();
+        assert_eq!(gridref.ancestry(), vec![gridref]);
+    }
+
+    #[test]
+    fn displays_all_constructible_references() {
+        // Sample the whole grid, including its far edges, at every precision.
+        let eastings = (0..super::WIDTH).step_by(7_919).chain([super::WIDTH - 1]);
+
+        for precision in Precision::ALL {
+            for e in eastings.clone() {
+                let northings = (0..super::HEIGHT).step_by(7_919).chain([super::HEIGHT - 1]);
+
+                for n in northings {
+                    let Ok(gridref) = OSI::new(e, n, *precision) else {
+                        continue;
+                    };
+
+                    assert_eq!(gridref.to_string().parse(), Ok(gridref.clone()));
+                    for offset in [(1, 1), (-1, -1)] {
+                        if let Ok(moved) = gridref.clone() + offset {
+                            assert_eq!(moved.to_string().parse(), Ok(moved));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_short_codes() {
+        for s in ["O", "O84", "O8943", "O892437", "O89294376", "O8929143762"] {
+            let gridref: OSI = s.parse().unwrap();
+            let code = gridref.to_short_code();
+
+            assert_eq!(code.len(), 9);
+            assert_eq!(OSI::from_short_code(&code), Ok(gridref.clone()));
+            assert_eq!(OSI::from_short_code(&code.to_lowercase()), Ok(gridref));
+        }
+
+        let far_corner = OSI::new(super::WIDTH - 1, super::HEIGHT - 1, Precision::_1M).unwrap();
+        assert_eq!(
+            OSI::from_short_code(&far_corner.to_short_code()),
+            Ok(far_corner)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_short_codes() {
+        assert!(matches!(
+            OSI::from_short_code("0000"),
+            Err(Error::ParseError(_))
+        ));
+        assert!(matches!(
+            OSI::from_short_code("00000000U"),
+            Err(Error::ParseError(_))
+        ));
+        // A valid precision code with eastings beyond the grid.
+        assert_eq!(OSI::from_short_code("Q00000000"), Err(Error::OutOfBounds));
+    }
+
+    #[test]
+    fn decodes_feature_precision_codes() {
+        let precision = |s: &str| OSI::from_short_code(s).map(|gridref| gridref.precision());
+
+        // Tetrads take precision code 6.
+        #[cfg(feature = "tetrads")]
+        assert_eq!(precision("R00000000"), Ok(Precision::_2Km));
+        #[cfg(not(feature = "tetrads"))]
+        assert_eq!(
+            precision("R00000000"),
+            Err(Error::InvalidPrecision(
+                "6 is not a valid precision code.".to_string()
+            ))
+        );
+
+        // Pentads take precision code 7, the last one.
+        #[cfg(feature = "pentads")]
+        assert_eq!(precision("W00000000"), Ok(Precision::_5Km));
+        #[cfg(not(feature = "pentads"))]
+        assert_eq!(
+            precision("W00000000"),
+            Err(Error::InvalidPrecision(
+                "7 is not a valid precision code.".to_string()
+            ))
+        );
+
+        // Code 7 with eastings beyond the grid.
+        #[cfg(feature = "pentads")]
+        assert_eq!(precision("Z00000000"), Err(Error::OutOfBounds));
+        #[cfg(not(feature = "pentads"))]
+        assert_eq!(
+            precision("Z00000000"),
+            Err(Error::InvalidPrecision(
+                "7 is not a valid precision code.".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn calculates_chebyshev_distance() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let distance = |s: &str| gridref.chebyshev_distance(&s.parse().unwrap());
+
+        assert_eq!(distance("O8943"), Some(0));
+        assert_eq!(distance("O9044"), Some(1));
+        assert_eq!(distance("O8842"), Some(1));
+        assert_eq!(distance("O9044"), distance("O8944"));
+        assert_eq!(distance("O9045"), Some(2));
+        assert_eq!(distance("O8741"), Some(2));
+        assert_eq!(distance("O892437"), None);
+    }
+
+    #[test]
+    fn calculates_manhattan_distance() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let distance = |s: &str| gridref.manhattan_distance(&s.parse().unwrap());
+
+        assert_eq!(distance("O8943"), Some(0));
+        assert_eq!(distance("O8944"), Some(1));
+        assert_eq!(distance("O8843"), Some(1));
+        assert_eq!(distance("O9044"), Some(2));
+        assert_eq!(distance("O8842"), Some(2));
+        assert_eq!(distance("O892437"), None);
+    }
+
+    #[test]
+    fn alternates_checker_parity() {
+        let gridref: OSI = "O8943".parse().unwrap();
+
+        for neighbour in gridref.edge_neighbours() {
+            assert_ne!(gridref.checker_parity(), neighbour.checker_parity());
+        }
+        let diagonal: OSI = "O9044".parse().unwrap();
+        assert_eq!(gridref.checker_parity(), diagonal.checker_parity());
+    }
+
+    #[test]
+    fn steps_toward_target() {
+        let start: OSI = "O8943".parse().unwrap();
+        let target: OSI = "O9540".parse().unwrap();
+
+        let mut current = start.clone();
+        let mut steps = 0;
+        while let Some(next) = current.step_toward(&target) {
+            assert_eq!(next.chebyshev_distance(&current), Some(1));
+            current = next;
+            steps += 1;
+        }
+
+        assert_eq!(current, target);
+        assert_eq!(steps, start.chebyshev_distance(&target).unwrap());
+        assert_eq!(target.step_toward(&target), None);
+        assert_eq!(start.step_toward(&"O892437".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn builds_pyramid() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let pyramid = gridref.pyramid(Precision::_10M);
+
+        assert_eq!(pyramid.len(), 2);
+        assert_eq!(pyramid[0].len(), 100);
+        assert_eq!(pyramid[1].len(), 10_000);
+        assert_eq!(pyramid[0][0].to_string(), "O890430");
+        assert_eq!(pyramid[0][1].to_string(), "O891430");
+        assert_eq!(pyramid[0][99].to_string(), "O899439");
+        assert_eq!(pyramid[1][9_999].to_string(), "O89994399");
+        assert!(pyramid
+            .iter()
+            .flatten()
+            .all(|child| child.recalculate(Precision::_1Km) == gridref));
+
+        assert!(gridref.pyramid(Precision::_1Km).is_empty());
+        assert!(gridref.pyramid(Precision::_10Km).is_empty());
+    }
+
+    #[test]
+    fn builds_plus() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let plus = gridref.plus(2);
+
+        assert_eq!(plus.len(), 5);
+        assert_eq!(plus[0], gridref);
+        for arm in &plus[1..] {
+            let (columns, rows) = gridref.global_cell_index();
+            let (arm_columns, arm_rows) = arm.global_cell_index();
+            assert_eq!(columns.abs_diff(arm_columns) + rows.abs_diff(arm_rows), 2);
+        }
+
+        assert_eq!(gridref.plus(0), vec![gridref]);
+    }
+
+    #[test]
+    fn omits_plus_arms_outside_grid() {
+        let gridref = OSI::new(0, 0, Precision::_1Km).unwrap();
+        let plus = gridref.plus(2);
+
+        assert_eq!(plus.len(), 3);
+        assert_eq!(plus[1], OSI::new(0, 2_000, Precision::_1Km).unwrap());
+        assert_eq!(plus[2], OSI::new(2_000, 0, Precision::_1Km).unwrap());
+    }
+
+    #[test]
+    fn quadrants_require_supported_precision() {
+        for gridref in ["O", "O8943"] {
+            let gridref: OSI = gridref.parse().unwrap();
+
+            assert!(gridref.quadrants().is_none());
+        }
+
+        // Pentads split 10km squares into quadrants.
+        let gridref: OSI = "O84".parse().unwrap();
+        assert_eq!(gridref.quadrants().is_some(), cfg!(feature = "pentads"));
+    }
+
+    #[test]
+    fn offsets_within_square() {
+        let gridref: OSI = "O892437".parse().unwrap();
+        assert_eq!(gridref.offset_in_square(), (89_200, 43_700));
+
+        let gridref: OSI = "O".parse().unwrap();
+        assert_eq!(gridref.offset_in_square(), (0, 0));
+    }
+}
+
+#[cfg(feature = "tetrads")]
+#[cfg(test)]
+mod test_tetrad {
+    use crate::{Precision, OSI};
+
+    #[test]
+    fn floors_unaligned_coordinates_to_tetrad() {
+        let built = OSI::new(227_345, 243_999, Precision::_2Km).unwrap();
+        let parsed: OSI = "N24R".parse().unwrap();
+
+        assert_eq!(built, parsed);
+        assert_eq!(built.to_string().parse::<OSI>().unwrap(), built);
+        assert!(built.is_2km_aligned());
+    }
+
+    #[test]
+    fn includes_tetrad_in_ancestry() {
+        let gridref: OSI = "O892437".parse().unwrap();
+        let ancestry: Vec<String> = gridref
+            .ancestry()
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+
+        #[cfg(not(feature = "pentads"))]
+        assert_eq!(ancestry, vec!["O892437", "O8943", "O84W", "O84", "O"]);
+        #[cfg(feature = "pentads")]
+        assert_eq!(
+            ancestry,
+            vec!["O892437", "O8943", "O84W", "O84SE", "O84", "O"]
+        );
+    }
+
+    #[test]
+    fn detects_2km_alignment() {
+        let aligned = OSI::new(226_000, 242_000, Precision::_1M).unwrap();
+        let unaligned = OSI::new(227_345, 243_999, Precision::_1M).unwrap();
+
+        assert!(aligned.is_2km_aligned());
+        assert!(!unaligned.is_2km_aligned());
+    }
+
+    #[test]
+    fn splits_tetrad_into_quadrants() {
+        let tetrad: OSI = "N24R".parse().unwrap();
+        let quadrants = tetrad.quadrants().unwrap();
+
+        let expected = ["N2642", "N2643", "N2743", "N2742"];
+        for (quadrant, expected) in quadrants.iter().zip(expected) {
+            assert_eq!(quadrant.precision(), Precision::_1Km);
+            assert_eq!(quadrant.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_reversed_tetrads() {
+        // Read reversed, these would hide OSGB 10km squares.
+        assert!("NS24".parse::<OSI>().is_err());
+        assert!("NR24".parse::<OSI>().is_err());
+        assert!("N24R".parse::<OSI>().is_ok());
+    }
+
+    #[test]
+    fn counts_tetrad_figures() {
+        let tetrad: OSI = "N24R".parse().unwrap();
+
+        assert_eq!(tetrad.figures(), 2);
+    }
+}
+
+#[cfg(feature = "pentads")]
+#[cfg(test)]
+mod test_pentad {
+    use crate::{Precision, OSI};
+
+    #[test]
+    fn round_trips_pentads() {
+        for pentad in ["O84SW", "O84SE", "O84NW", "O84NE"] {
+            let gridref: OSI = pentad.parse().unwrap();
+
+            assert_eq!(gridref.precision(), Precision::_5Km);
+            assert_eq!(gridref.to_string(), pentad);
+            assert_eq!(gridref.figures(), 2);
+        }
+    }
+
+    #[test]
+    fn floors_unaligned_coordinates_to_pentad() {
+        let built = OSI::new(389_345, 248_999, Precision::_5Km).unwrap();
+        let parsed: OSI = "o84 ne".parse().unwrap();
+
+        assert_eq!(built, parsed);
+        assert_eq!(built.to_string(), "O84NE");
+    }
+
+    #[test]
+    fn nests_pentads_between_10km_and_1km() {
+        let gridref: OSI = "O892437".parse().unwrap();
+        let pentad = gridref.recalculate(Precision::_5Km);
+
+        assert_eq!(pentad.to_string(), "O84SE");
+        assert_eq!(
+            gridref.recalculate(Precision::_10Km),
+            pentad.recalculate(Precision::_10Km)
+        );
+        assert_eq!(pentad.children(Precision::_1Km).len(), 25);
+    }
+
+    #[test]
+    #[cfg(feature = "tetrads")]
+    fn skips_tetrads_within_pentads() {
+        let pentad: OSI = "O84SE".parse().unwrap();
+
+        // 2km tetrads do not evenly tile a 5km pentad.
+        assert!(pentad.children(Precision::_2Km).is_empty());
+        assert!(pentad
+            .children_excluding(&pentad, Precision::_2Km)
+            .is_empty());
+
+        let pyramid = pentad.pyramid(Precision::_100M);
+        let sizes: Vec<usize> = pyramid.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![25, 2_500]);
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -1,10 +1,13 @@
 use crate::constants::*;
+use crate::{utils, Error};
 
 /// Supported 'resolutions' for grid references.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 pub enum Precision {
     _100Km,
     _10Km,
+    #[cfg(feature = "pentads")]
+    _5Km,
     #[cfg(feature = "tetrads")]
     _2Km,
     _1Km,
@@ -14,6 +17,20 @@ pub enum Precision {
 }
 
 impl Precision {
+    /// All supported precisions, from coarsest to finest.
+    pub(crate) const ALL: &'static [Precision] = &[
+        Precision::_100Km,
+        Precision::_10Km,
+        #[cfg(feature = "pentads")]
+        Precision::_5Km,
+        #[cfg(feature = "tetrads")]
+        Precision::_2Km,
+        Precision::_1Km,
+        Precision::_100M,
+        Precision::_10M,
+        Precision::_1M,
+    ];
+
     /// Returns the Precision in metres
     ///
     /// # Example
@@ -25,6 +42,8 @@ impl Precision {
         match self {
             Precision::_100Km => _100KM,
             Precision::_10Km => _10KM,
+            #[cfg(feature = "pentads")]
+            Precision::_5Km => _5KM,
             #[cfg(feature = "tetrads")]
             Precision::_2Km => _2KM,
             Precision::_1Km => _1KM,
@@ -34,6 +53,58 @@ impl Precision {
         }
     }
 
+    /// Returns the Precision matching the given
+    /// number of metres, if there is one.
+    pub(crate) fn from_metres(metres: u32) -> Option<Self> {
+        match metres {
+            _100KM => Some(Precision::_100Km),
+            _10KM => Some(Precision::_10Km),
+            #[cfg(feature = "pentads")]
+            _5KM => Some(Precision::_5Km),
+            #[cfg(feature = "tetrads")]
+            _2KM => Some(Precision::_2Km),
+            _1KM => Some(Precision::_1Km),
+            _100M => Some(Precision::_100M),
+            _10M => Some(Precision::_10M),
+            _1M => Some(Precision::_1M),
+            _ => None,
+        }
+    }
+
+    /// Returns a stable byte code for the Precision,
+    /// used in fixed-size binary encodings.
+    pub(crate) fn code(&self) -> u8 {
+        match self {
+            Precision::_100Km => 0,
+            Precision::_10Km => 1,
+            Precision::_1Km => 2,
+            Precision::_100M => 3,
+            Precision::_10M => 4,
+            Precision::_1M => 5,
+            #[cfg(feature = "tetrads")]
+            Precision::_2Km => 6,
+            #[cfg(feature = "pentads")]
+            Precision::_5Km => 7,
+        }
+    }
+
+    /// Returns the Precision matching the given byte code, if there is one.
+    pub(crate) fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Precision::_100Km),
+            1 => Some(Precision::_10Km),
+            2 => Some(Precision::_1Km),
+            3 => Some(Precision::_100M),
+            4 => Some(Precision::_10M),
+            5 => Some(Precision::_1M),
+            #[cfg(feature = "tetrads")]
+            6 => Some(Precision::_2Km),
+            #[cfg(feature = "pentads")]
+            7 => Some(Precision::_5Km),
+            _ => None,
+        }
+    }
+
     /// Returns the number of digits needed to represent
     /// a grid reference with this precision
     ///
@@ -46,6 +117,8 @@ impl Precision {
         match self {
             Precision::_100Km => 0,
             Precision::_10Km => 2,
+            #[cfg(feature = "pentads")]
+            Precision::_5Km => 2,
             #[cfg(feature = "tetrads")]
             Precision::_2Km => 2,
             Precision::_1Km => 4,
@@ -54,12 +127,91 @@ impl Precision {
             Precision::_1M => 10,
         }
     }
+
+    /// Returns a human readable label for the precision's cell size,
+    /// for display rather than parsing.
+    ///
+    /// # Example
+    /// ```
+    /// # use gridish::Precision;
+    /// assert_eq!(Precision::_100M.label(), "100 m");
+    /// ```
+    pub fn label(&self) -> &'static str {
+        match self {
+            Precision::_100Km => "100 km",
+            Precision::_10Km => "10 km",
+            #[cfg(feature = "pentads")]
+            Precision::_5Km => "5 km",
+            #[cfg(feature = "tetrads")]
+            Precision::_2Km => "2 km",
+            Precision::_1Km => "1 km",
+            Precision::_100M => "100 m",
+            Precision::_10M => "10 m",
+            Precision::_1M => "1 m",
+        }
+    }
+
+    /// Returns how many cells of the other precision fit along
+    /// one side of a cell of this precision, if this precision is
+    /// coarser and evenly divisible by the other.
+    ///
+    /// # Example
+    /// ```
+    /// # use gridish::Precision;
+    /// assert_eq!(Precision::_10Km.ratio_to(&Precision::_1Km), Some(10));
+    /// assert_eq!(Precision::_1Km.ratio_to(&Precision::_10Km), None);
+    /// ```
+    pub fn ratio_to(&self, other: &Precision) -> Option<u32> {
+        if self >= other || !self.metres().is_multiple_of(other.metres()) {
+            return None;
+        }
+
+        Some(self.metres() / other.metres())
+    }
+}
+
+/// Returns the precision implied by the digit portion of a grid
+/// reference, without its leading square letters, such as "892437".
+/// An empty string implies `Precision::_100Km`.
+///
+/// # Errors
+/// Returns an error if the string contains anything other than
+/// digits, or is not an even length of at most 10 digits.
+///
+/// # Example
+/// ```
+/// use gridish::{precision_from_digits_str, Precision};
+///
+/// assert_eq!(precision_from_digits_str("892437").unwrap(), Precision::_100M);
+/// assert!(precision_from_digits_str("89243").is_err());
+/// ```
+pub fn precision_from_digits_str(digits: &str) -> Result<Precision, Error> {
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(Error::ParseError(format!("{c} is not a valid digit.")));
+    }
+
+    let (_, _, precision) = utils::digits(digits)?;
+
+    Ok(precision)
+}
+
+/// Returns whether the crate was built with the `tetrads` feature,
+/// and so supports `Precision::_2Km` grid references.
+///
+/// # Example
+/// ```
+/// use gridish::tetrads_enabled;
+///
+/// assert_eq!(tetrads_enabled(), cfg!(feature = "tetrads"));
+/// ```
+pub fn tetrads_enabled() -> bool {
+    cfg!(feature = "tetrads")
 }
 
 #[cfg(test)]
 mod test {
     use crate::constants::*;
-    use crate::Precision;
+    use crate::{precision_from_digits_str, tetrads_enabled, Error, Precision};
 
     #[test]
     fn converts_to_metres() {
@@ -70,4 +222,137 @@ mod test {
         assert_eq!(Precision::_10M.metres(), 10);
         assert_eq!(Precision::_1M.metres(), 1);
     }
+
+    #[test]
+    fn labels_precisions() {
+        assert_eq!(Precision::_100Km.label(), "100 km");
+        assert_eq!(Precision::_10Km.label(), "10 km");
+        assert_eq!(Precision::_1Km.label(), "1 km");
+        assert_eq!(Precision::_100M.label(), "100 m");
+        assert_eq!(Precision::_10M.label(), "10 m");
+        assert_eq!(Precision::_1M.label(), "1 m");
+    }
+
+    #[test]
+    fn orders_all_precisions() {
+        assert!(Precision::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(Precision::ALL
+            .windows(2)
+            .all(|pair| pair[0].metres() > pair[1].metres()));
+    }
+
+    #[test]
+    fn converts_from_metres() {
+        assert_eq!(Precision::from_metres(_100KM), Some(Precision::_100Km));
+        assert_eq!(Precision::from_metres(_1KM), Some(Precision::_1Km));
+        assert_eq!(Precision::from_metres(_1M), Some(Precision::_1M));
+        assert_eq!(Precision::from_metres(500), None);
+    }
+
+    #[test]
+    fn round_trips_codes() {
+        for precision in [
+            Precision::_100Km,
+            Precision::_10Km,
+            Precision::_1Km,
+            Precision::_100M,
+            Precision::_10M,
+            Precision::_1M,
+        ] {
+            assert_eq!(Precision::from_code(precision.code()), Some(precision));
+        }
+
+        assert_eq!(Precision::from_code(8), None);
+    }
+
+    #[test]
+    fn reads_precision_from_digits() {
+        assert_eq!(precision_from_digits_str("892437"), Ok(Precision::_100M));
+        assert_eq!(precision_from_digits_str("8943"), Ok(Precision::_1Km));
+        assert_eq!(precision_from_digits_str(""), Ok(Precision::_100Km));
+        assert!(matches!(
+            precision_from_digits_str("89243"),
+            Err(Error::ParseError(_))
+        ));
+        assert!(matches!(
+            precision_from_digits_str("+89243"),
+            Err(Error::ParseError(_))
+        ));
+        assert!(matches!(
+            precision_from_digits_str("892437892437"),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn reports_tetrads_enabled() {
+        assert_eq!(tetrads_enabled(), cfg!(feature = "tetrads"));
+        assert_eq!(
+            tetrads_enabled(),
+            Precision::ALL
+                .iter()
+                .any(|precision| precision.metres() == _2KM),
+            "tetrads add a 2km precision"
+        );
+    }
+
+    #[test]
+    fn calculates_ratio_to_finer_precision() {
+        assert_eq!(Precision::_10Km.ratio_to(&Precision::_1Km), Some(10));
+        assert_eq!(Precision::_100Km.ratio_to(&Precision::_1M), Some(100_000));
+        assert_eq!(Precision::_1Km.ratio_to(&Precision::_10Km), None);
+        assert_eq!(Precision::_1Km.ratio_to(&Precision::_1Km), None);
+    }
+}
+
+#[cfg(feature = "tetrads")]
+#[cfg(test)]
+mod test_tetrad {
+    use crate::constants::*;
+    use crate::Precision;
+
+    #[test]
+    fn converts_tetrad_to_metres() {
+        assert_eq!(Precision::_2Km.metres(), _2KM);
+        assert_eq!(Precision::_2Km.digits(), 2);
+        assert_eq!(Precision::from_metres(_2KM), Some(Precision::_2Km));
+        assert_eq!(
+            Precision::from_code(Precision::_2Km.code()),
+            Some(Precision::_2Km)
+        );
+        assert_eq!(Precision::_2Km.label(), "2 km");
+    }
+
+    #[test]
+    fn calculates_tetrad_ratio() {
+        assert_eq!(Precision::_10Km.ratio_to(&Precision::_2Km), Some(5));
+        assert_eq!(Precision::_2Km.ratio_to(&Precision::_1Km), Some(2));
+        assert_eq!(Precision::_2Km.ratio_to(&Precision::_10Km), None);
+    }
+}
+
+#[cfg(feature = "pentads")]
+#[cfg(test)]
+mod test_pentad {
+    use crate::constants::*;
+    use crate::Precision;
+
+    #[test]
+    fn converts_pentad_to_metres() {
+        assert_eq!(Precision::_5Km.metres(), _5KM);
+        assert_eq!(Precision::_5Km.digits(), 2);
+        assert_eq!(Precision::_5Km.label(), "5 km");
+        assert_eq!(Precision::from_metres(_5KM), Some(Precision::_5Km));
+        assert_eq!(
+            Precision::from_code(Precision::_5Km.code()),
+            Some(Precision::_5Km)
+        );
+    }
+
+    #[test]
+    fn calculates_pentad_ratio() {
+        assert_eq!(Precision::_10Km.ratio_to(&Precision::_5Km), Some(2));
+        assert_eq!(Precision::_5Km.ratio_to(&Precision::_1Km), Some(5));
+        assert_eq!(Precision::_5Km.ratio_to(&Precision::_10Km), None);
+    }
 }
@@ -0,0 +1,1167 @@
+use crate::constants::_100KM;
+use crate::utils::line_cells;
+use crate::{Precision, OSGB, OSI};
+use geo_types::{coord, Point, Rect};
+use std::collections::{BTreeSet, HashSet};
+
+/// Returns the distinct British National Grid References at the
+/// given precision that together contain all of the given references,
+/// in the order they are first encountered.
+///
+/// # Example
+/// ```
+/// use gridish::{coarse_cover_osgb, Precision, OSGB};
+///
+/// let gridrefs: Vec<OSGB> = ["SO892437", "SO895431", "SO902437"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// let cover = coarse_cover_osgb(gridrefs, Precision::_1Km);
+///
+/// assert_eq!(cover.len(), 2);
+/// ```
+pub fn coarse_cover_osgb<I: IntoIterator<Item = OSGB>>(refs: I, precision: Precision) -> Vec<OSGB> {
+    distinct(
+        refs.into_iter()
+            .map(|gridref| gridref.recalculate(precision)),
+    )
+}
+
+/// Returns the distinct Irish National Grid References at the
+/// given precision that together contain all of the given references,
+/// in the order they are first encountered.
+///
+/// # Example
+/// ```
+/// use gridish::{coarse_cover_osi, Precision, OSI};
+///
+/// let gridrefs: Vec<OSI> = ["O892437", "O895431", "O902437"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// let cover = coarse_cover_osi(gridrefs, Precision::_1Km);
+///
+/// assert_eq!(cover.len(), 2);
+/// ```
+pub fn coarse_cover_osi<I: IntoIterator<Item = OSI>>(refs: I, precision: Precision) -> Vec<OSI> {
+    distinct(
+        refs.into_iter()
+            .map(|gridref| gridref.recalculate(precision)),
+    )
+}
+
+/// Returns the contiguous path of British National Grid References connecting
+/// the given references in order, drawing a straight line of cells
+/// between each consecutive pair. Shared endpoints appear once.
+/// All references are recalculated to the coarsest precision among
+/// them, and any cells falling outside of the grid are skipped.
+///
+/// # Example
+/// ```
+/// use gridish::{densify_path_osgb, OSGB};
+///
+/// let track: Vec<OSGB> = ["SO8943", "SO9143", "SO9144"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// let path: Vec<String> = densify_path_osgb(track)
+///     .iter()
+///     .map(|gridref| gridref.to_string())
+///     .collect();
+///
+/// assert_eq!(path, vec!["SO8943", "SO9043", "SO9143", "SO9144"]);
+/// ```
+pub fn densify_path_osgb<I: IntoIterator<Item = OSGB>>(points: I) -> Vec<OSGB> {
+    let points: Vec<OSGB> = points.into_iter().collect();
+    let Some(precision) = points.iter().map(|point| point.precision()).min() else {
+        return vec![];
+    };
+
+    path_cells(
+        points
+            .iter()
+            .map(|point| point.recalculate(precision).global_cell_index()),
+    )
+    .into_iter()
+    .filter_map(|(column, row)| OSGB::from_global_cell_index(column, row, precision).ok())
+    .collect()
+}
+
+/// Returns the contiguous path of Irish National Grid References connecting
+/// the given references in order, drawing a straight line of cells
+/// between each consecutive pair. Shared endpoints appear once.
+/// All references are recalculated to the coarsest precision among
+/// them, and any cells falling outside of the grid are skipped.
+///
+/// # Example
+/// ```
+/// use gridish::{densify_path_osi, OSI};
+///
+/// let track: Vec<OSI> = ["O8943", "O9143", "O9144"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// let path: Vec<String> = densify_path_osi(track)
+///     .iter()
+///     .map(|gridref| gridref.to_string())
+///     .collect();
+///
+/// assert_eq!(path, vec!["O8943", "O9043", "O9143", "O9144"]);
+/// ```
+pub fn densify_path_osi<I: IntoIterator<Item = OSI>>(points: I) -> Vec<OSI> {
+    let points: Vec<OSI> = points.into_iter().collect();
+    let Some(precision) = points.iter().map(|point| point.precision()).min() else {
+        return vec![];
+    };
+
+    path_cells(
+        points
+            .iter()
+            .map(|point| point.recalculate(precision).global_cell_index()),
+    )
+    .into_iter()
+    .filter_map(|(column, row)| OSI::from_global_cell_index(column, row, precision).ok())
+    .collect()
+}
+
+/// Returns the smallest rect covering all of the given British National Grid
+/// References in full, whatever their precisions, expanding by each
+/// reference's own south west and north east corners.
+/// Returns `None` if there are no references.
+///
+/// # Example
+/// ```
+/// use gridish::{bounding_region_mixed_osgb, OSGB};
+/// use geo_types::{coord, Rect};
+///
+/// let gridrefs: Vec<OSGB> = ["SO84", "SO954437"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(
+///     bounding_region_mixed_osgb(gridrefs),
+///     Some(Rect::new(
+///         coord! { x: 380_000.0, y: 240_000.0 },
+///         coord! { x: 395_500.0, y: 250_000.0 },
+///     ))
+/// );
+/// ```
+pub fn bounding_region_mixed_osgb<I: IntoIterator<Item = OSGB>>(refs: I) -> Option<Rect> {
+    bounding_rect(refs.into_iter().map(|gridref| (gridref.sw(), gridref.ne())))
+}
+
+/// Returns the smallest rect covering all of the given Irish National Grid
+/// References in full, whatever their precisions, expanding by each
+/// reference's own south west and north east corners.
+/// Returns `None` if there are no references.
+///
+/// # Example
+/// ```
+/// use gridish::{bounding_region_mixed_osi, OSI};
+/// use geo_types::{coord, Rect};
+///
+/// let gridrefs: Vec<OSI> = ["O84", "O954437"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(
+///     bounding_region_mixed_osi(gridrefs),
+///     Some(Rect::new(
+///         coord! { x: 380_000.0, y: 240_000.0 },
+///         coord! { x: 395_500.0, y: 250_000.0 },
+///     ))
+/// );
+/// ```
+pub fn bounding_region_mixed_osi<I: IntoIterator<Item = OSI>>(refs: I) -> Option<Rect> {
+    bounding_rect(refs.into_iter().map(|gridref| (gridref.sw(), gridref.ne())))
+}
+
+/// Returns the 100km British National Grid squares that the given rect
+/// overlaps, at [`Precision::_100Km`], running west to east along each row
+/// from south to north. Squares that only touch an edge of the rect,
+/// and squares outside of the grid, are skipped.
+///
+/// # Example
+/// ```
+/// use gridish::squares_in_rect_osgb;
+/// use geo_types::{coord, Rect};
+///
+/// let rect = Rect::new(
+///     coord! { x: 390_000.0, y: 240_000.0 },
+///     coord! { x: 410_000.0, y: 250_000.0 },
+/// );
+/// let squares: Vec<String> = squares_in_rect_osgb(rect)
+///     .iter()
+///     .map(|gridref| gridref.to_string())
+///     .collect();
+///
+/// assert_eq!(squares, ["SO", "SP"]);
+/// ```
+pub fn squares_in_rect_osgb(rect: Rect) -> Vec<OSGB> {
+    // The grid is 10 squares wide and 15 high.
+    square_cells(rect, 10, 15)
+        .filter_map(|(column, row)| {
+            OSGB::new(column * _100KM, row * _100KM, Precision::_100Km).ok()
+        })
+        .collect()
+}
+
+/// Returns the 100km Irish National Grid squares that the given rect
+/// overlaps, at [`Precision::_100Km`], running west to east along each row
+/// from south to north. Squares that only touch an edge of the rect,
+/// and squares outside of the grid, are skipped.
+///
+/// # Example
+/// ```
+/// use gridish::squares_in_rect_osi;
+/// use geo_types::{coord, Rect};
+///
+/// let rect = Rect::new(
+///     coord! { x: 390_000.0, y: 240_000.0 },
+///     coord! { x: 410_000.0, y: 250_000.0 },
+/// );
+/// let squares: Vec<String> = squares_in_rect_osi(rect)
+///     .iter()
+///     .map(|gridref| gridref.to_string())
+///     .collect();
+///
+/// assert_eq!(squares, ["O", "P"]);
+/// ```
+pub fn squares_in_rect_osi(rect: Rect) -> Vec<OSI> {
+    // The grid is 5 squares wide and 5 high.
+    square_cells(rect, 5, 5)
+        .filter_map(|(column, row)| OSI::new(column * _100KM, row * _100KM, Precision::_100Km).ok())
+        .collect()
+}
+
+/// Returns axis-aligned rects covering exactly the same area as the
+/// given British National Grid References, merging neighbouring cells into
+/// as few rects as a row-then-column merge finds.
+/// All references are recalculated to the coarsest precision among them.
+///
+/// # Example
+/// ```
+/// use gridish::{coalesce_osgb, OSGB};
+/// use geo_types::{coord, Rect};
+///
+/// let gridrefs: Vec<OSGB> = ["SO8943", "SO9043", "SO8944", "SO9044"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(
+///     coalesce_osgb(gridrefs),
+///     vec![Rect::new(
+///         coord! { x: 389_000.0, y: 243_000.0 },
+///         coord! { x: 391_000.0, y: 245_000.0 },
+///     )]
+/// );
+/// ```
+pub fn coalesce_osgb<I: IntoIterator<Item = OSGB>>(refs: I) -> Vec<Rect> {
+    let refs: Vec<OSGB> = refs.into_iter().collect();
+    let Some(precision) = refs.iter().map(|gridref| gridref.precision()).min() else {
+        return vec![];
+    };
+
+    coalesce_cells(
+        refs.iter()
+            .map(|gridref| gridref.recalculate(precision).global_cell_index())
+            .collect(),
+        precision,
+    )
+}
+
+/// Returns axis-aligned rects covering exactly the same area as the
+/// given Irish National Grid References, merging neighbouring cells into
+/// as few rects as a row-then-column merge finds.
+/// All references are recalculated to the coarsest precision among them.
+///
+/// # Example
+/// ```
+/// use gridish::{coalesce_osi, OSI};
+/// use geo_types::{coord, Rect};
+///
+/// let gridrefs: Vec<OSI> = ["O8943", "O9043", "O8944", "O9044"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(
+///     coalesce_osi(gridrefs),
+///     vec![Rect::new(
+///         coord! { x: 389_000.0, y: 243_000.0 },
+///         coord! { x: 391_000.0, y: 245_000.0 },
+///     )]
+/// );
+/// ```
+pub fn coalesce_osi<I: IntoIterator<Item = OSI>>(refs: I) -> Vec<Rect> {
+    let refs: Vec<OSI> = refs.into_iter().collect();
+    let Some(precision) = refs.iter().map(|gridref| gridref.precision()).min() else {
+        return vec![];
+    };
+
+    coalesce_cells(
+        refs.iter()
+            .map(|gridref| gridref.recalculate(precision).global_cell_index())
+            .collect(),
+        precision,
+    )
+}
+
+/// Merges cells into rects, first joining runs of cells along each
+/// row, then stacking runs of the same span in consecutive rows.
+fn coalesce_cells(cells: BTreeSet<(u32, u32)>, precision: Precision) -> Vec<Rect> {
+    // Runs along each row, as (first column, last column, row).
+    let mut runs: Vec<(u32, u32, u32)> = vec![];
+    let mut by_row: Vec<(u32, u32)> = cells.into_iter().map(|(c, r)| (r, c)).collect();
+    by_row.sort_unstable();
+
+    for (row, column) in by_row {
+        match runs.last_mut() {
+            Some((_, last, run_row)) if *run_row == row && *last + 1 == column => *last = column,
+            _ => runs.push((column, column, row)),
+        }
+    }
+
+    // Rects, as (first column, last column, first row, last row).
+    let mut rects: Vec<(u32, u32, u32, u32)> = vec![];
+    runs.sort_unstable();
+
+    for (first, last, row) in runs {
+        match rects.last_mut() {
+            Some((rect_first, rect_last, _, last_row))
+                if (*rect_first, *rect_last) == (first, last) && *last_row + 1 == row =>
+            {
+                *last_row = row
+            }
+            _ => rects.push((first, last, row, row)),
+        }
+    }
+
+    let metres = f64::from(precision.metres());
+    rects
+        .into_iter()
+        .map(|(first, last, first_row, last_row)| {
+            Rect::new(
+                coord! { x: f64::from(first) * metres, y: f64::from(first_row) * metres },
+                coord! { x: f64::from(last + 1) * metres, y: f64::from(last_row + 1) * metres },
+            )
+        })
+        .collect()
+}
+
+/// Returns the British National Grid References missing from the given
+/// references, within the smallest block of cells containing them all.
+/// All references are recalculated to the coarsest precision among them,
+/// and the gaps are ordered from south to north, then west to east.
+///
+/// # Example
+/// ```
+/// use gridish::{find_gaps_osgb, OSGB};
+///
+/// let gridrefs: Vec<OSGB> = ["SO8943", "SO9043", "SO8944"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// let gaps: Vec<String> = find_gaps_osgb(gridrefs)
+///     .iter()
+///     .map(|gridref| gridref.to_string())
+///     .collect();
+///
+/// assert_eq!(gaps, vec!["SO9044"]);
+/// ```
+pub fn find_gaps_osgb<I: IntoIterator<Item = OSGB>>(refs: I) -> Vec<OSGB> {
+    let refs: Vec<OSGB> = refs.into_iter().collect();
+    let Some(precision) = refs.iter().map(|gridref| gridref.precision()).min() else {
+        return vec![];
+    };
+
+    missing_cells(
+        refs.iter()
+            .map(|gridref| gridref.recalculate(precision).global_cell_index())
+            .collect(),
+    )
+    .into_iter()
+    .filter_map(|(column, row)| OSGB::from_global_cell_index(column, row, precision).ok())
+    .collect()
+}
+
+/// Returns the Irish National Grid References missing from the given
+/// references, within the smallest block of cells containing them all.
+/// All references are recalculated to the coarsest precision among them,
+/// and the gaps are ordered from south to north, then west to east.
+///
+/// # Example
+/// ```
+/// use gridish::{find_gaps_osi, OSI};
+///
+/// let gridrefs: Vec<OSI> = ["O8943", "O9043", "O8944"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// let gaps: Vec<String> = find_gaps_osi(gridrefs)
+///     .iter()
+///     .map(|gridref| gridref.to_string())
+///     .collect();
+///
+/// assert_eq!(gaps, vec!["O9044"]);
+/// ```
+pub fn find_gaps_osi<I: IntoIterator<Item = OSI>>(refs: I) -> Vec<OSI> {
+    let refs: Vec<OSI> = refs.into_iter().collect();
+    let Some(precision) = refs.iter().map(|gridref| gridref.precision()).min() else {
+        return vec![];
+    };
+
+    missing_cells(
+        refs.iter()
+            .map(|gridref| gridref.recalculate(precision).global_cell_index())
+            .collect(),
+    )
+    .into_iter()
+    .filter_map(|(column, row)| OSI::from_global_cell_index(column, row, precision).ok())
+    .collect()
+}
+
+/// Returns the cells within the bounds of the given cells that are
+/// not among them, ordered by row, then column.
+fn missing_cells(cells: HashSet<(u32, u32)>) -> Vec<(u32, u32)> {
+    let Some(((first_column, first_row), (last_column, last_row))) = cell_bounds(&cells) else {
+        return vec![];
+    };
+
+    (first_row..=last_row)
+        .flat_map(|row| (first_column..=last_column).map(move |column| (column, row)))
+        .filter(|cell| !cells.contains(cell))
+        .collect()
+}
+
+/// Renders the given British National Grid References as a block of text for
+/// debugging, with '#' for each cell present and '.' for each absent
+/// within the smallest block containing them all, north at the top.
+/// All references are recalculated to the coarsest precision among them.
+///
+/// # Example
+/// ```
+/// use gridish::{ascii_grid_osgb, OSGB};
+///
+/// let gridrefs: Vec<OSGB> = ["SO8943", "SO9043", "SO8944"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(ascii_grid_osgb(gridrefs), "#.\n##");
+/// ```
+pub fn ascii_grid_osgb<I: IntoIterator<Item = OSGB>>(refs: I) -> String {
+    let refs: Vec<OSGB> = refs.into_iter().collect();
+    let Some(precision) = refs.iter().map(|gridref| gridref.precision()).min() else {
+        return String::new();
+    };
+
+    render_cells(
+        refs.iter()
+            .map(|gridref| gridref.recalculate(precision).global_cell_index())
+            .collect(),
+    )
+}
+
+/// Renders the given Irish National Grid References as a block of text for
+/// debugging, with '#' for each cell present and '.' for each absent
+/// within the smallest block containing them all, north at the top.
+/// All references are recalculated to the coarsest precision among them.
+///
+/// # Example
+/// ```
+/// use gridish::{ascii_grid_osi, OSI};
+///
+/// let gridrefs: Vec<OSI> = ["O8943", "O9043", "O8944"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(ascii_grid_osi(gridrefs), "#.\n##");
+/// ```
+pub fn ascii_grid_osi<I: IntoIterator<Item = OSI>>(refs: I) -> String {
+    let refs: Vec<OSI> = refs.into_iter().collect();
+    let Some(precision) = refs.iter().map(|gridref| gridref.precision()).min() else {
+        return String::new();
+    };
+
+    render_cells(
+        refs.iter()
+            .map(|gridref| gridref.recalculate(precision).global_cell_index())
+            .collect(),
+    )
+}
+
+/// Returns the rect spanning every given pair of
+/// south west and north east corners, or `None` if there are none.
+fn bounding_rect(corners: impl Iterator<Item = (Point, Point)>) -> Option<Rect> {
+    corners
+        .map(|(sw, ne)| Rect::new(sw.0, ne.0))
+        .reduce(|bounds, rect| {
+            Rect::new(
+                coord! { x: bounds.min().x.min(rect.min().x), y: bounds.min().y.min(rect.min().y) },
+                coord! { x: bounds.max().x.max(rect.max().x), y: bounds.max().y.max(rect.max().y) },
+            )
+        })
+}
+
+/// Returns the column and row of each 100km square the rect overlaps,
+/// within a grid of the given number of columns and rows.
+fn square_cells(rect: Rect, columns: u32, rows: u32) -> impl Iterator<Item = (u32, u32)> {
+    let span = |min: f64, max: f64, count: u32| {
+        let size = f64::from(_100KM);
+        let first = (min / size).floor();
+        let last = ((max / size).ceil() - 1.0)
+            .max(first)
+            .min(f64::from(count - 1));
+
+        // Casting is safe here as both ends are clamped to the grid.
+        (first.max(0.0) as i64..=last as i64).map(|cell| cell as u32)
+    };
+    let span_x = span(rect.min().x, rect.max().x, columns);
+
+    span(rect.min().y, rect.max().y, rows)
+        .flat_map(move |row| span_x.clone().map(move |column| (column, row)))
+}
+
+/// Returns the first and last column and row of the cells,
+/// or `None` if there are none.
+fn cell_bounds(cells: &HashSet<(u32, u32)>) -> Option<((u32, u32), (u32, u32))> {
+    let columns = cells.iter().map(|cell| cell.0);
+    let rows = cells.iter().map(|cell| cell.1);
+
+    Some((
+        (columns.clone().min()?, rows.clone().min()?),
+        (columns.max()?, rows.max()?),
+    ))
+}
+
+/// Renders the cells within their bounds as rows of '#' and '.',
+/// from the northernmost row down.
+fn render_cells(cells: HashSet<(u32, u32)>) -> String {
+    let Some(((first_column, first_row), (last_column, last_row))) = cell_bounds(&cells) else {
+        return String::new();
+    };
+
+    (first_row..=last_row)
+        .rev()
+        .map(|row| {
+            (first_column..=last_column)
+                .map(|column| {
+                    if cells.contains(&(column, row)) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Joins consecutive cells with straight lines of cells,
+/// without repeating the cell shared by each pair of lines.
+fn path_cells(cells: impl Iterator<Item = (u32, u32)>) -> Vec<(u32, u32)> {
+    let mut path: Vec<(u32, u32)> = vec![];
+
+    for cell in cells {
+        match path.last() {
+            Some(&last) => path.extend(line_cells(last, cell).into_iter().skip(1)),
+            None => path.push(cell),
+        }
+    }
+
+    path
+}
+
+/// Returns the British National Grid References present in both `a` and `b`,
+/// in the order they appear in `a`.
+///
+/// # Example
+/// ```
+/// use gridish::{intersect_sets_osgb, OSGB};
+///
+/// let a: Vec<OSGB> = ["SO8943", "SO9043"].iter().map(|s| s.parse().unwrap()).collect();
+/// let b: Vec<OSGB> = ["SO9043", "SO9143"].iter().map(|s| s.parse().unwrap()).collect();
+///
+/// assert_eq!(intersect_sets_osgb(&a, &b), vec![a[1].clone()]);
+/// ```
+pub fn intersect_sets_osgb(a: &[OSGB], b: &[OSGB]) -> Vec<OSGB> {
+    filter_membership(a, b, true)
+}
+
+/// Returns the British National Grid References present in `a` but not in `b`,
+/// in the order they appear in `a`.
+///
+/// # Example
+/// ```
+/// use gridish::{difference_sets_osgb, OSGB};
+///
+/// let a: Vec<OSGB> = ["SO8943", "SO9043"].iter().map(|s| s.parse().unwrap()).collect();
+/// let b: Vec<OSGB> = ["SO9043", "SO9143"].iter().map(|s| s.parse().unwrap()).collect();
+///
+/// assert_eq!(difference_sets_osgb(&a, &b), vec![a[0].clone()]);
+/// ```
+pub fn difference_sets_osgb(a: &[OSGB], b: &[OSGB]) -> Vec<OSGB> {
+    filter_membership(a, b, false)
+}
+
+/// Returns the Irish National Grid References present in both `a` and `b`,
+/// in the order they appear in `a`.
+///
+/// # Example
+/// ```
+/// use gridish::{intersect_sets_osi, OSI};
+///
+/// let a: Vec<OSI> = ["O8943", "O9043"].iter().map(|s| s.parse().unwrap()).collect();
+/// let b: Vec<OSI> = ["O9043", "O9143"].iter().map(|s| s.parse().unwrap()).collect();
+///
+/// assert_eq!(intersect_sets_osi(&a, &b), vec![a[1].clone()]);
+/// ```
+pub fn intersect_sets_osi(a: &[OSI], b: &[OSI]) -> Vec<OSI> {
+    filter_membership(a, b, true)
+}
+
+/// Returns the Irish National Grid References present in `a` but not in `b`,
+/// in the order they appear in `a`.
+///
+/// # Example
+/// ```
+/// use gridish::{difference_sets_osi, OSI};
+///
+/// let a: Vec<OSI> = ["O8943", "O9043"].iter().map(|s| s.parse().unwrap()).collect();
+/// let b: Vec<OSI> = ["O9043", "O9143"].iter().map(|s| s.parse().unwrap()).collect();
+///
+/// assert_eq!(difference_sets_osi(&a, &b), vec![a[0].clone()]);
+/// ```
+pub fn difference_sets_osi(a: &[OSI], b: &[OSI]) -> Vec<OSI> {
+    filter_membership(a, b, false)
+}
+
+/// Keeps the items of `a` whose membership of `b` matches `keep`.
+fn filter_membership<T: Clone + Eq + std::hash::Hash>(a: &[T], b: &[T], keep: bool) -> Vec<T> {
+    let b: HashSet<&T> = b.iter().collect();
+
+    a.iter()
+        .filter(|item| b.contains(item) == keep)
+        .cloned()
+        .collect()
+}
+
+/// Returns the British National Grid References whose eight neighbours at the
+/// same precision, including diagonals, are all among the given
+/// references, in the order they are given.
+///
+/// # Example
+/// ```
+/// use gridish::{interior_cells_osgb, OSGB};
+///
+/// let block: Vec<OSGB> = ["SO8842", "SO8942", "SO9042", "SO8843", "SO8943", "SO9043", "SO8844", "SO8944", "SO9044"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(interior_cells_osgb(&block), vec![block[4].clone()]);
+/// ```
+pub fn interior_cells_osgb(refs: &[OSGB]) -> Vec<OSGB> {
+    filter_interior(
+        refs,
+        |gridref| (gridref.global_cell_index(), gridref.precision()),
+        true,
+    )
+}
+
+/// Returns the British National Grid References missing at least one of their
+/// eight neighbours at the same precision, including diagonals,
+/// from the given references, in the order they are given.
+/// The complement of [`interior_cells_osgb`].
+///
+/// # Example
+/// ```
+/// use gridish::{boundary_cells_osgb, OSGB};
+///
+/// let gridrefs: Vec<OSGB> = ["SO8943", "SO9043"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(boundary_cells_osgb(&gridrefs), gridrefs);
+/// ```
+pub fn boundary_cells_osgb(refs: &[OSGB]) -> Vec<OSGB> {
+    filter_interior(
+        refs,
+        |gridref| (gridref.global_cell_index(), gridref.precision()),
+        false,
+    )
+}
+
+/// Returns the Irish National Grid References whose eight neighbours at the
+/// same precision, including diagonals, are all among the given
+/// references, in the order they are given.
+///
+/// # Example
+/// ```
+/// use gridish::{interior_cells_osi, OSI};
+///
+/// let block: Vec<OSI> = ["O8842", "O8942", "O9042", "O8843", "O8943", "O9043", "O8844", "O8944", "O9044"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(interior_cells_osi(&block), vec![block[4].clone()]);
+/// ```
+pub fn interior_cells_osi(refs: &[OSI]) -> Vec<OSI> {
+    filter_interior(
+        refs,
+        |gridref| (gridref.global_cell_index(), gridref.precision()),
+        true,
+    )
+}
+
+/// Returns the Irish National Grid References missing at least one of their
+/// eight neighbours at the same precision, including diagonals,
+/// from the given references, in the order they are given.
+/// The complement of [`interior_cells_osi`].
+///
+/// # Example
+/// ```
+/// use gridish::{boundary_cells_osi, OSI};
+///
+/// let gridrefs: Vec<OSI> = ["O8943", "O9043"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(boundary_cells_osi(&gridrefs), gridrefs);
+/// ```
+pub fn boundary_cells_osi(refs: &[OSI]) -> Vec<OSI> {
+    filter_interior(
+        refs,
+        |gridref| (gridref.global_cell_index(), gridref.precision()),
+        false,
+    )
+}
+
+/// Keeps the items whose cells' eight neighbours are all present,
+/// if `interior`, or otherwise those missing any neighbour.
+fn filter_interior<T: Clone>(
+    refs: &[T],
+    cell: impl Fn(&T) -> ((u32, u32), Precision),
+    interior: bool,
+) -> Vec<T> {
+    let cells: HashSet<((u32, u32), Precision)> = refs.iter().map(&cell).collect();
+    let has_all_neighbours = |((column, row), precision): ((u32, u32), Precision)| {
+        (-1..=1)
+            .flat_map(|columns| (-1..=1).map(move |rows| (columns, rows)))
+            .filter(|offset| *offset != (0, 0))
+            .all(|(columns, rows)| {
+                match (
+                    column.checked_add_signed(columns),
+                    row.checked_add_signed(rows),
+                ) {
+                    (Some(column), Some(row)) => cells.contains(&((column, row), precision)),
+                    _ => false,
+                }
+            })
+    };
+
+    refs.iter()
+        .filter(|item| has_all_neighbours(cell(item)) == interior)
+        .cloned()
+        .collect()
+}
+
+/// Removes duplicates, keeping the first of each.
+fn distinct<T: Clone + Eq + std::hash::Hash>(items: impl Iterator<Item = T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+
+    items.filter(|item| seen.insert(item.clone())).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ascii_grid_osgb, ascii_grid_osi, boundary_cells_osgb, boundary_cells_osi,
+        bounding_region_mixed_osgb, bounding_region_mixed_osi, coalesce_osgb, coalesce_osi,
+        coarse_cover_osgb, coarse_cover_osi, densify_path_osgb, densify_path_osi,
+        difference_sets_osgb, difference_sets_osi, find_gaps_osgb, find_gaps_osi,
+        interior_cells_osgb, interior_cells_osi, intersect_sets_osgb, intersect_sets_osi,
+        squares_in_rect_osgb, squares_in_rect_osi, Precision, OSGB, OSI,
+    };
+    use geo_types::{coord, Rect};
+    use std::collections::HashSet;
+
+    #[test]
+    fn covers_osgb_with_coarse_cells() {
+        let gridrefs: Vec<OSGB> = ["SO892437", "SO902437", "SO895431", "SO909499", "SO8943"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        let cover: Vec<String> = coarse_cover_osgb(gridrefs.clone(), Precision::_1Km)
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+        assert_eq!(cover, vec!["SO8943", "SO9043", "SO9049"]);
+
+        let cover: Vec<String> = coarse_cover_osgb(gridrefs, Precision::_10Km)
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+        assert_eq!(cover, vec!["SO84", "SO94"]);
+    }
+
+    #[test]
+    fn covers_osi_with_coarse_cells() {
+        let gridrefs: Vec<OSI> = ["O892437", "O902437", "O895431", "O901439"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        let cover: Vec<String> = coarse_cover_osi(gridrefs, Precision::_1Km)
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+        assert_eq!(cover, vec!["O8943", "O9043"]);
+    }
+
+    #[test]
+    fn densifies_osgb_zig_zag() {
+        let track: Vec<OSGB> = ["SO8943", "SO9246", "SO9543"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let path = densify_path_osgb(track);
+
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first().unwrap().to_string(), "SO8943");
+        assert_eq!(path[3].to_string(), "SO9246");
+        assert_eq!(path.last().unwrap().to_string(), "SO9543");
+        assert_eq!(path.iter().collect::<HashSet<_>>().len(), path.len());
+        assert!(path.windows(2).all(|pair| {
+            let (a, b) = (pair[0].global_cell_index(), pair[1].global_cell_index());
+            a.0.abs_diff(b.0) <= 1 && a.1.abs_diff(b.1) <= 1
+        }));
+    }
+
+    #[test]
+    fn densifies_osi_zig_zag() {
+        let track: Vec<OSI> = ["O8943", "O9246", "O9543"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let path = densify_path_osi(track);
+
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.iter().collect::<HashSet<_>>().len(), path.len());
+        assert!(path.windows(2).all(|pair| {
+            let (a, b) = (pair[0].global_cell_index(), pair[1].global_cell_index());
+            a.0.abs_diff(b.0) <= 1 && a.1.abs_diff(b.1) <= 1
+        }));
+    }
+
+    #[test]
+    fn densifies_empty_path() {
+        assert!(densify_path_osgb(vec![]).is_empty());
+        assert!(densify_path_osi(vec![]).is_empty());
+    }
+
+    #[test]
+    fn coalesces_osgb_block() {
+        let block: Vec<OSGB> = (0..3)
+            .flat_map(|row| (0..3).map(move |column| (column, row)))
+            .map(|(column, row)| {
+                OSGB::new(
+                    389_000 + column * 100,
+                    243_000 + row * 100,
+                    Precision::_100M,
+                )
+            })
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(
+            coalesce_osgb(block.clone()),
+            vec![Rect::new(
+                coord! { x: 389_000.0, y: 243_000.0 },
+                coord! { x: 389_300.0, y: 243_300.0 },
+            )]
+        );
+
+        // Removing the middle cell leaves a ring of four rects.
+        let ring: Vec<OSGB> = block
+            .into_iter()
+            .filter(|gridref| gridref.to_string() != "SO891431")
+            .collect();
+        let rects = coalesce_osgb(ring);
+        assert_eq!(rects.len(), 4);
+        assert_eq!(
+            rects
+                .iter()
+                .map(|rect| rect.width() * rect.height())
+                .sum::<f64>(),
+            80_000.0
+        );
+
+        assert!(coalesce_osgb(vec![]).is_empty());
+    }
+
+    #[test]
+    fn coalesces_osi_l_shape() {
+        let gridrefs: Vec<OSI> = ["O8943", "O9043", "O9143", "O8944", "O8943"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        assert_eq!(
+            coalesce_osi(gridrefs),
+            vec![
+                Rect::new(
+                    coord! { x: 389_000.0, y: 244_000.0 },
+                    coord! { x: 390_000.0, y: 245_000.0 },
+                ),
+                Rect::new(
+                    coord! { x: 389_000.0, y: 243_000.0 },
+                    coord! { x: 392_000.0, y: 244_000.0 },
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_gap_in_osgb_block() {
+        let block: Vec<OSGB> = [
+            "SO8943", "SO9043", "SO9143", "SO8944", "SO9144", "SO8945", "SO9045", "SO9145",
+        ]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+        let gaps: Vec<String> = find_gaps_osgb(block)
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+
+        assert_eq!(gaps, vec!["SO9044"]);
+        assert!(find_gaps_osgb(vec!["SO8943".parse().unwrap()]).is_empty());
+        assert!(find_gaps_osgb(vec![]).is_empty());
+    }
+
+    #[test]
+    fn finds_gaps_in_osi_diagonal() {
+        let gridrefs: Vec<OSI> = ["O8943", "O9044"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let gaps: Vec<String> = find_gaps_osi(gridrefs)
+            .iter()
+            .map(|gridref| gridref.to_string())
+            .collect();
+
+        assert_eq!(gaps, vec!["O9043", "O8944"]);
+    }
+
+    #[test]
+    fn renders_osgb_l_shape() {
+        let gridrefs: Vec<OSGB> = ["SO8943", "SO9043", "SO9143", "SO8944", "SO8945"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        assert_eq!(ascii_grid_osgb(gridrefs), "#..\n#..\n###");
+        assert_eq!(ascii_grid_osgb(vec!["SO8943".parse().unwrap()]), "#");
+        assert_eq!(ascii_grid_osgb(vec![]), "");
+    }
+
+    #[test]
+    fn renders_osi_at_coarsest_precision() {
+        let gridrefs: Vec<OSI> = ["O892437", "O8943", "O9044"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        assert_eq!(ascii_grid_osi(gridrefs), ".#\n#.");
+    }
+
+    #[test]
+    fn intersects_osgb_sets() {
+        let a: Vec<OSGB> = ["SO8943", "SO9043", "SO892437", "SO9143", "SO9043"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let b: Vec<OSGB> = ["SO9143", "SO9043", "SO9243"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let to_strings = |gridrefs: Vec<OSGB>| -> Vec<String> {
+            gridrefs.iter().map(|gridref| gridref.to_string()).collect()
+        };
+
+        assert_eq!(
+            to_strings(intersect_sets_osgb(&a, &b)),
+            vec!["SO9043", "SO9143", "SO9043"]
+        );
+        assert_eq!(
+            to_strings(difference_sets_osgb(&a, &b)),
+            vec!["SO8943", "SO892437"]
+        );
+        assert!(intersect_sets_osgb(&a, &[]).is_empty());
+        assert_eq!(difference_sets_osgb(&a, &[]), a);
+    }
+
+    #[test]
+    fn intersects_osi_sets() {
+        let a: Vec<OSI> = ["O8943", "O9043"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let b: Vec<OSI> = ["O9043", "O9143"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        assert_eq!(intersect_sets_osi(&a, &b), vec![a[1].clone()]);
+        assert_eq!(difference_sets_osi(&a, &b), vec![a[0].clone()]);
+    }
+
+    #[test]
+    fn erodes_osgb_block() {
+        let block: Vec<OSGB> = (0..3)
+            .flat_map(|row| (0..3).map(move |column| (column, row)))
+            .map(|(column, row)| {
+                OSGB::from_global_cell_index(388 + column, 242 + row, Precision::_1Km)
+            })
+            .map(Result::unwrap)
+            .collect();
+
+        let interior = interior_cells_osgb(&block);
+        assert_eq!(interior.len(), 1);
+        assert_eq!(interior[0].to_string(), "SO8943");
+
+        let boundary = boundary_cells_osgb(&block);
+        assert_eq!(boundary.len(), 8);
+        assert!(!boundary.contains(&interior[0]));
+
+        // Neighbours must share the precision.
+        let mut mixed = block.clone();
+        mixed[0] = mixed[0].recalculate(Precision::_10Km);
+        assert!(interior_cells_osgb(&mixed).is_empty());
+    }
+
+    #[test]
+    fn erodes_osi_at_grid_edge() {
+        let block: Vec<OSI> = (0..3)
+            .flat_map(|row| (0..3).map(move |column| (column, row)))
+            .map(|(column, row)| OSI::from_global_cell_index(column, row, Precision::_1Km))
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(interior_cells_osi(&block), vec![block[4].clone()]);
+        assert_eq!(boundary_cells_osi(&block).len(), 8);
+        assert!(interior_cells_osi(&[]).is_empty());
+    }
+
+    #[test]
+    fn bounds_mixed_osgb_precisions() {
+        let coarse: OSGB = "SO84".parse().unwrap();
+        let fine: OSGB = "SO954437".parse().unwrap();
+        let bounds = bounding_region_mixed_osgb([fine.clone(), coarse.clone()]).unwrap();
+
+        for gridref in [coarse, fine] {
+            assert!(gridref.within_rect(bounds));
+        }
+        assert_eq!(bounds.min(), coord! { x: 380_000.0, y: 240_000.0 });
+        assert_eq!(bounds.max(), coord! { x: 395_500.0, y: 250_000.0 });
+
+        assert_eq!(bounding_region_mixed_osgb(vec![]), None);
+    }
+
+    #[test]
+    fn bounds_mixed_osi_precisions() {
+        let coarse: OSI = "O84".parse().unwrap();
+        let fine: OSI = "O954437".parse().unwrap();
+        let bounds = bounding_region_mixed_osi([fine.clone(), coarse.clone()]).unwrap();
+
+        for gridref in [coarse, fine] {
+            assert!(gridref.within_rect(bounds));
+        }
+        assert_eq!(bounds.min(), coord! { x: 380_000.0, y: 240_000.0 });
+        assert_eq!(bounds.max(), coord! { x: 395_500.0, y: 250_000.0 });
+
+        assert_eq!(bounding_region_mixed_osi(vec![]), None);
+    }
+
+    #[test]
+    fn finds_osgb_squares_in_rect() {
+        let squares = |min: (f64, f64), max: (f64, f64)| -> Vec<String> {
+            let rect = Rect::new(coord! { x: min.0, y: min.1 }, coord! { x: max.0, y: max.1 });
+
+            squares_in_rect_osgb(rect)
+                .iter()
+                .map(|gridref| gridref.to_string())
+                .collect()
+        };
+
+        // Straddling two squares, and all four around a corner.
+        assert_eq!(
+            squares((390_000.0, 240_000.0), (410_000.0, 250_000.0)),
+            ["SO", "SP"]
+        );
+        assert_eq!(
+            squares((390_000.0, 290_000.0), (410_000.0, 310_000.0)),
+            ["SO", "SP", "SJ", "SK"]
+        );
+        // Touching the edge of a square does not overlap it.
+        assert_eq!(
+            squares((300_000.0, 200_000.0), (400_000.0, 300_000.0)),
+            ["SO"]
+        );
+        // Squares off the grid, or in the unused J square, are skipped.
+        assert_eq!(squares((-50_000.0, 0.0), (50_000.0, 50_000.0)), ["SV"]);
+        assert!(squares((-200_000.0, -200_000.0), (-100_000.0, -100_000.0)).is_empty());
+        assert!(squares((500_000.0, 1_300_000.0), (600_000.0, 1_400_000.0)).is_empty());
+
+        for gridref in squares_in_rect_osgb(Rect::new(
+            coord! { x: 0.0, y: 0.0 },
+            coord! { x: 2_000_000.0, y: 2_000_000.0 },
+        )) {
+            assert_eq!(gridref.precision(), Precision::_100Km);
+        }
+    }
+
+    #[test]
+    fn finds_osi_squares_in_rect() {
+        let squares = |min: (f64, f64), max: (f64, f64)| -> Vec<String> {
+            let rect = Rect::new(coord! { x: min.0, y: min.1 }, coord! { x: max.0, y: max.1 });
+
+            squares_in_rect_osi(rect)
+                .iter()
+                .map(|gridref| gridref.to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            squares((390_000.0, 240_000.0), (410_000.0, 250_000.0)),
+            ["O", "P"]
+        );
+        assert_eq!(
+            squares((300_000.0, 200_000.0), (400_000.0, 300_000.0)),
+            ["O"]
+        );
+        assert_eq!(
+            squares((450_000.0, 450_000.0), (600_000.0, 600_000.0)),
+            ["E"]
+        );
+        assert_eq!(
+            squares_in_rect_osi(Rect::new(
+                coord! { x: 0.0, y: 0.0 },
+                coord! { x: 500_000.0, y: 500_000.0 },
+            ))
+            .len(),
+            25
+        );
+    }
+}
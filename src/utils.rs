@@ -1,17 +1,27 @@
 use crate::{Error, Precision};
+use geo_types::{Coord, LineString, Point, Polygon, Rect};
+use std::collections::HashSet;
 
 /// Converts the digits of a string into
 /// eastings, northings and precision.
 pub fn digits(s: &str) -> Result<(u32, u32, Precision), Error> {
-    // Error is s length is over 10 or not even;
-    if s.len() > 10 || (s.len() % 2) != 0 {
+    // Error if s length is over 10, as it may contain more than one grid reference.
+    if s.len() > 10 {
+        return Err(Error::ParseError(format!(
+            "{} digits exceeds the maximum of 10 significant digits (1m precision).",
+            s.len()
+        )));
+    }
+
+    // Error if s length is not even.
+    if !s.len().is_multiple_of(2) {
         return Err(Error::ParseError(format!(
             "{} is not a valid number of digits. Supported values: 0, 2, 4, 6, 8, 10.",
             s.len()
         )));
     }
 
-    let (eastings, northings) = {
+    let (eastings, northings): (u32, u32) = {
         if s.is_empty() {
             (0, 0)
         } else {
@@ -41,31 +51,474 @@ pub fn digits(s: &str) -> Result<(u32, u32, Precision), Error> {
         }
     };
 
+    // The length check above bounds both values, but guard
+    // against overflow rather than risk a panic.
+    Ok((
+        eastings
+            .checked_mul(precision.metres())
+            .ok_or(Error::OutOfBounds)?,
+        northings
+            .checked_mul(precision.metres())
+            .ok_or(Error::OutOfBounds)?,
+        precision,
+    ))
+}
+
+/// Converts a single group of digits, such as the eastings,
+/// into metres and the precision implied by its length.
+pub fn digit_group(s: &str) -> Result<(u32, Precision), Error> {
+    // Integer parsing accepts a leading sign, which is not a digit.
+    if let Some(c) = s.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(Error::ParseError(format!("{c} is not a valid digit.")));
+    }
+
+    let precision = match s.len() {
+        1 => Precision::_10Km,
+        2 => Precision::_1Km,
+        3 => Precision::_100M,
+        4 => Precision::_10M,
+        5 => Precision::_1M,
+        _ => {
+            return Err(Error::InvalidPrecision(format!(
+                "{} is not a valid number of digits. Supported values: 1, 2, 3, 4, 5.",
+                s.len()
+            )))
+        }
+    };
+
+    let metres: u32 = s
+        .parse()
+        .map_err(|e| Error::ParseError(format!("{:?}", e)))?;
+
     Ok((
-        eastings * precision.metres(),
-        northings * precision.metres(),
+        metres
+            .checked_mul(precision.metres())
+            .ok_or(Error::OutOfBounds)?,
         precision,
     ))
 }
 
-/// Removes all non-alphanumeric characters from string
-/// and converts to uppercase for parsing.
+/// Characters used to encode short codes, omitting the easily
+/// confused I, L, O and U, as in Crockford's base32.
+const BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Encodes the value as a fixed number of base32 characters,
+/// most significant first.
+pub fn encode_base32(value: u64, len: usize) -> String {
+    (0..len)
+        .rev()
+        .map(|i| char::from(BASE32[((value >> (i * 5)) & 31) as usize]))
+        .collect()
+}
+
+/// Decodes base32 characters, as created by `encode_base32`,
+/// ignoring case.
+pub fn decode_base32(s: &str) -> Result<u64, Error> {
+    if s.len() > 12 {
+        return Err(Error::ParseError(format!(
+            "{} characters is too long for a short code.",
+            s.len()
+        )));
+    }
+
+    s.chars().try_fold(0, |value, c| {
+        let digit = BASE32
+            .iter()
+            .position(|b| char::from(*b) == c.to_ascii_uppercase())
+            .ok_or_else(|| {
+                Error::ParseError(format!("{c} is not a valid short code character."))
+            })?;
+
+        Ok((value << 5) | digit as u64)
+    })
+}
+
+/// Writes formatted text into a fixed size byte buffer,
+/// failing if the text does not fit.
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl std::fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let end = self.len + s.len();
+        let target = self.buf.get_mut(self.len..end).ok_or(std::fmt::Error)?;
+
+        target.copy_from_slice(s.as_bytes());
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+/// Characters accepted as separators between the parts
+/// of a grid reference, in addition to ASCII whitespace.
+const SEPARATORS: [char; 2] = ['-', '/'];
+
+/// Removes all ASCII whitespace and separator characters
+/// ('-' and '/') from string and converts to uppercase for parsing.
+/// Any other characters are kept, to be rejected when parsed.
 pub fn trim_string(s: &str) -> String {
     s.chars()
-        .filter(|c| !c.is_ascii_whitespace())
+        .filter(|c| !c.is_ascii_whitespace() && !SEPARATORS.contains(c))
         .map(|c| c.to_ascii_uppercase())
         .collect()
 }
 
+/// The characters a check character is computed over, and drawn from.
+const CHECK_ALPHABET: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Returns the check character for a string of digits and uppercase
+/// letters, using the Luhn mod N algorithm with N = 36.
+///
+/// Each character is mapped to its position in `0-9A-Z`. Working from
+/// the rightmost character, positions are alternately doubled, starting
+/// with the rightmost, and each result's base 36 digits summed into a
+/// total. The check character is the one whose position, added to the
+/// total, makes it a multiple of 36. This catches any single mistyped
+/// character and most swaps of neighbouring characters.
+///
+/// Returns `None` if the string contains any other character.
+pub fn check_character(s: &str) -> Option<char> {
+    let n = CHECK_ALPHABET.len();
+    let mut factor = 2;
+    let mut total = 0;
+
+    for c in s.bytes().rev() {
+        let position = CHECK_ALPHABET.iter().position(|x| *x == c)?;
+        let addend = position * factor;
+
+        total += addend / n + addend % n;
+        factor = 3 - factor;
+    }
+
+    Some(char::from(CHECK_ALPHABET[(n - total % n) % n]))
+}
+
+/// Checks that when the digits of a grid reference are split into
+/// groups by whitespace or separators, there are exactly two groups,
+/// of the same length. Groups such as "SO8 437" or "SO8 43 7" suggest
+/// a mistyped grid reference, which would otherwise be read as "SO8437".
+pub fn check_digit_groups(s: &str) -> Result<(), Error> {
+    let groups: Vec<usize> = s
+        .split(|c: char| c.is_ascii_whitespace() || SEPARATORS.contains(&c))
+        .map(|group| group.chars().filter(|c| c.is_ascii_digit()).count())
+        .filter(|digits| *digits > 0)
+        .collect();
+
+    match groups.as_slice() {
+        [] | [_] => Ok(()),
+        [eastings, northings] if eastings == northings => Ok(()),
+        [eastings, northings] => Err(Error::ParseError(format!(
+            "Digit groups of {eastings} and {northings} digits are ambiguous."
+        ))),
+        groups => Err(Error::ParseError(format!(
+            "{} digit groups are ambiguous. Expected eastings and northings.",
+            groups.len()
+        ))),
+    }
+}
+
+/// Returns the straight line distance between two points.
+pub fn distance(a: &Point, b: &Point) -> f64 {
+    (a.x() - b.x()).hypot(a.y() - b.y())
+}
+
+/// Returns the cells on a straight line between two cells,
+/// inclusive of both, using Bresenham's line algorithm.
+/// Each cell touches the previous one at an edge or corner.
+pub fn line_cells(from: (u32, u32), to: (u32, u32)) -> Vec<(u32, u32)> {
+    let (mut x, mut y) = (i64::from(from.0), i64::from(from.1));
+    let (x1, y1) = (i64::from(to.0), i64::from(to.1));
+
+    let dx = (x1 - x).abs();
+    let dy = -(y1 - y).abs();
+    let step_x = if x < x1 { 1 } else { -1 };
+    let step_y = if y < y1 { 1 } else { -1 };
+    let mut error = dx + dy;
+    let mut cells = vec![];
+
+    loop {
+        // Casting is safe here as the line never leaves the bounds of its ends.
+        cells.push((x as u32, y as u32));
+
+        if x == x1 && y == y1 {
+            return cells;
+        }
+
+        // Both steps are decided by the error before either is taken.
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+/// Returns the indices of the tiles of the given size, counted from
+/// the origin, that the span from `min` to `max` overlaps along one
+/// axis, each with the length of the overlap. Tiles only touching
+/// the span at its ends are left out.
+pub fn axis_tiles(min: f64, max: f64, origin: f64, size: f64) -> Vec<(i64, f64)> {
+    let first = ((min - origin) / size).floor() as i64;
+    let last = ((max - origin) / size).ceil() as i64;
+
+    (first..last)
+        .map(|index| {
+            let start = origin + index as f64 * size;
+            let overlap = max.min(start + size) - min.max(start);
+
+            (index, overlap)
+        })
+        .filter(|(_, overlap)| *overlap > 0.0)
+        .collect()
+}
+
+/// Returns the distinct cell offsets on the outline of a circle of
+/// the given radius, in cells, around the origin, using the midpoint
+/// circle algorithm. Ordered from south to north, then west to east.
+pub fn circle_cells(radius: u32) -> Vec<(i64, i64)> {
+    let (mut x, mut y) = (i64::from(radius), 0);
+    let mut decision = 1 - x;
+    let mut cells = vec![];
+
+    while x >= y {
+        for (column, row) in [(x, y), (y, x)] {
+            cells.extend([
+                (column, row),
+                (-column, row),
+                (column, -row),
+                (-column, -row),
+            ]);
+        }
+
+        y += 1;
+        if decision < 0 {
+            decision += 2 * y + 1;
+        } else {
+            x -= 1;
+            decision += 2 * (y - x) + 1;
+        }
+    }
+
+    cells.sort_by_key(|(column, row)| (*row, *column));
+    cells.dedup();
+    cells
+}
+
+/// Returns the distinct cells of the given size that the line string
+/// passes through, in the order they are first reached.
+/// All of the line string's coordinates must be non-negative.
+pub fn line_string_cells(line: &LineString, size: f64) -> Vec<(u32, u32)> {
+    let mut seen = HashSet::new();
+    let cells = match line.0.as_slice() {
+        [] => vec![],
+        [only] => vec![cell_at(*only, size)],
+        coords => coords
+            .windows(2)
+            .flat_map(|pair| segment_cells(pair[0], pair[1], size))
+            .collect(),
+    };
+
+    cells
+        .into_iter()
+        .filter(|cell| seen.insert(*cell))
+        .collect()
+}
+
+/// Returns the cell of the given size containing the coordinate.
+fn cell_at(c: Coord, size: f64) -> (u32, u32) {
+    ((c.x / size) as u32, (c.y / size) as u32)
+}
+
+/// Walks the cells the segment passes through, from a to b,
+/// using the Amanatides-Woo grid traversal algorithm.
+fn segment_cells(a: Coord, b: Coord, size: f64) -> Vec<(u32, u32)> {
+    let (mut column, mut row) = cell_at(a, size);
+    let (end_column, end_row) = cell_at(b, size);
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+
+    // The distance along the segment, as a fraction, to the
+    // next cell boundary on each axis, and between boundaries.
+    let next_boundary = |start: f64, cell: u32, delta: f64| {
+        if delta > 0.0 {
+            (f64::from(cell + 1) * size - start) / delta
+        } else if delta < 0.0 {
+            (f64::from(cell) * size - start) / delta
+        } else {
+            f64::INFINITY
+        }
+    };
+    let mut t_max_x = next_boundary(a.x, column, dx);
+    let mut t_max_y = next_boundary(a.y, row, dy);
+    let (t_delta_x, t_delta_y) = (size / dx.abs(), size / dy.abs());
+
+    let steps = column.abs_diff(end_column) + row.abs_diff(end_row);
+    let mut cells = vec![(column, row)];
+
+    for _ in 0..steps {
+        if t_max_x < t_max_y && column != end_column || row == end_row {
+            t_max_x += t_delta_x;
+            column = if dx > 0.0 { column + 1 } else { column - 1 };
+        } else {
+            t_max_y += t_delta_y;
+            row = if dy > 0.0 { row + 1 } else { row - 1 };
+        }
+
+        cells.push((column, row));
+    }
+
+    cells
+}
+
+/// Returns whether the point lies within the polygon,
+/// using the even-odd ray casting rule.
+/// Points falling within any of the polygon's holes are excluded.
+pub fn polygon_contains(polygon: &Polygon, point: &Point) -> bool {
+    ring_contains(polygon.exterior(), point)
+        && !polygon
+            .interiors()
+            .iter()
+            .any(|ring| ring_contains(ring, point))
+}
+
+/// Returns the area of the polygon lying within the rect,
+/// excluding any of the polygon's holes.
+pub fn clipped_area(polygon: &Polygon, rect: &Rect) -> f64 {
+    let hole_area: f64 = polygon
+        .interiors()
+        .iter()
+        .map(|ring| ring_area(&clip_ring(ring, rect)))
+        .sum();
+
+    (ring_area(&clip_ring(polygon.exterior(), rect)) - hole_area).max(0.0)
+}
+
+/// Clips the ring to the rect using the Sutherland-Hodgman algorithm,
+/// one edge of the rect at a time.
+fn clip_ring(ring: &LineString, rect: &Rect) -> Vec<Coord> {
+    let (min, max) = (rect.min(), rect.max());
+    let mut vertices: Vec<Coord> = ring.coords().copied().collect();
+
+    // Each edge is given as whether it is vertical, where it lies,
+    // and which side of it is inside the rect.
+    let edges = [
+        (true, min.x, 1.0),
+        (true, max.x, -1.0),
+        (false, min.y, 1.0),
+        (false, max.y, -1.0),
+    ];
+
+    for (vertical, bound, side) in edges {
+        let inside = |c: Coord| side * (if vertical { c.x } else { c.y } - bound) >= 0.0;
+        let crossing = |a, b| {
+            if vertical {
+                crossing_x(a, b, bound)
+            } else {
+                crossing_y(a, b, bound)
+            }
+        };
+        let input = std::mem::take(&mut vertices);
+
+        for (i, &current) in input.iter().enumerate() {
+            let previous = input[(i + input.len() - 1) % input.len()];
+
+            match (inside(previous), inside(current)) {
+                (true, true) => vertices.push(current),
+                (true, false) => vertices.push(crossing(previous, current)),
+                (false, true) => {
+                    vertices.push(crossing(previous, current));
+                    vertices.push(current);
+                }
+                (false, false) => {}
+            }
+        }
+    }
+
+    vertices
+}
+
+/// Returns where the line from a to b crosses the vertical line at x.
+fn crossing_x(a: Coord, b: Coord, x: f64) -> Coord {
+    let t = (x - a.x) / (b.x - a.x);
+
+    Coord {
+        x,
+        y: a.y + t * (b.y - a.y),
+    }
+}
+
+/// Returns where the line from a to b crosses the horizontal line at y.
+fn crossing_y(a: Coord, b: Coord, y: f64) -> Coord {
+    let t = (y - a.y) / (b.y - a.y);
+
+    Coord {
+        x: a.x + t * (b.x - a.x),
+        y,
+    }
+}
+
+/// Returns the unsigned area enclosed by the vertices,
+/// using the shoelace formula.
+fn ring_area(vertices: &[Coord]) -> f64 {
+    let twice_area: f64 = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum();
+
+    twice_area.abs() / 2.0
+}
+
+/// Casts a ray east from the point, counting
+/// how many of the ring's edges it crosses.
+fn ring_contains(ring: &LineString, point: &Point) -> bool {
+    let (x, y) = point.x_y();
+    let mut inside = false;
+
+    for line in ring.lines() {
+        let (x1, y1) = line.start.x_y();
+        let (x2, y2) = line.end.x_y();
+
+        if (y1 > y) != (y2 > y) && x < (x2 - x1) * (y - y1) / (y2 - y1) + x1 {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
         constants::*,
-        utils::{digits, trim_string},
+        utils::{
+            axis_tiles, check_character, check_digit_groups, circle_cells, clipped_area,
+            decode_base32, digit_group, digits, distance, encode_base32, line_cells,
+            line_string_cells, polygon_contains, trim_string,
+        },
         Error, Precision,
     };
+    use geo_types::{coord, LineString, Point, Polygon, Rect};
 
     #[test]
+    #[allow(clippy::zero_prefixed_literal)]
     fn parse_valid_digits() {
         assert_eq!(digits(""), Ok((0, 0, Precision::_100Km)));
         assert_eq!(digits("12"), Ok((_10KM, 20_000, Precision::_10Km)));
@@ -86,6 +539,20 @@ mod test {
             ))
         );
 
+        // Reject too many digits
+        assert_eq!(
+            digits("123456789012"),
+            Err(Error::ParseError(
+                "12 digits exceeds the maximum of 10 significant digits (1m precision)."
+                    .to_string()
+            ))
+        );
+
+        // Reject long even length numbers without overflowing
+        let long = "9".repeat(40);
+        assert!(matches!(digits(&long), Err(Error::ParseError(_))));
+        assert_eq!(digits("9999999999"), Ok((99_999, 99_999, Precision::_1M)));
+
         // Reject non numbers
         assert_eq!(
             digits("ab"),
@@ -95,10 +562,221 @@ mod test {
         )
     }
 
+    #[test]
+    fn parse_digit_groups() {
+        assert_eq!(digit_group("8"), Ok((80_000, Precision::_10Km)));
+        assert_eq!(digit_group("89"), Ok((89_000, Precision::_1Km)));
+        assert_eq!(digit_group("892"), Ok((89_200, Precision::_100M)));
+        assert_eq!(digit_group("8929"), Ok((89_290, Precision::_10M)));
+        assert_eq!(digit_group("89291"), Ok((89_291, Precision::_1M)));
+
+        assert_eq!(
+            digit_group("892910"),
+            Err(Error::InvalidPrecision(
+                "6 is not a valid number of digits. Supported values: 1, 2, 3, 4, 5.".to_string()
+            ))
+        );
+        assert_eq!(
+            digit_group("8a"),
+            Err(Error::ParseError("a is not a valid digit.".to_string()))
+        );
+        assert_eq!(
+            digit_group("+12"),
+            Err(Error::ParseError("+ is not a valid digit.".to_string()))
+        );
+    }
+
+    #[test]
+    fn round_trips_base32() {
+        assert_eq!(encode_base32(0, 3), "000");
+        assert_eq!(encode_base32(31, 2), "0Z");
+        assert_eq!(encode_base32(32, 2), "10");
+        assert_eq!(decode_base32("10"), Ok(32));
+        assert_eq!(decode_base32("0z"), Ok(31));
+        assert_eq!(
+            decode_base32(&encode_base32(123_456_789, 9)),
+            Ok(123_456_789)
+        );
+
+        assert!(matches!(decode_base32("0U"), Err(Error::ParseError(_))));
+        assert!(matches!(
+            decode_base32("0000000000000"),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn computes_check_characters() {
+        assert_eq!(check_character("SO892437"), Some('E'));
+        assert_eq!(check_character("SO8943"), Some('V'));
+        assert_eq!(check_character(""), Some('0'));
+        assert_eq!(check_character("so8943"), None);
+
+        // Swapping neighbouring characters changes the check character.
+        assert_ne!(check_character("SO892473"), check_character("SO892437"));
+    }
+
+    #[test]
+    fn checks_digit_groups() {
+        assert_eq!(check_digit_groups("SO 892 437"), Ok(()));
+        assert_eq!(check_digit_groups("SO84 37"), Ok(()));
+        assert_eq!(check_digit_groups("SO892437"), Ok(()));
+        assert_eq!(check_digit_groups("SO 892437"), Ok(()));
+        assert_eq!(check_digit_groups("SO-89-43"), Ok(()));
+        assert_eq!(check_digit_groups("SO"), Ok(()));
+        assert_eq!(
+            check_digit_groups("SO8 437"),
+            Err(Error::ParseError(
+                "Digit groups of 1 and 3 digits are ambiguous.".to_string()
+            ))
+        );
+        assert!(check_digit_groups("SO 89/4").is_err());
+        assert_eq!(
+            check_digit_groups("SO8 43 7"),
+            Err(Error::ParseError(
+                "3 digit groups are ambiguous. Expected eastings and northings.".to_string()
+            ))
+        );
+        assert!(check_digit_groups("SO 89 43 89 43").is_err());
+    }
+
     #[test]
     fn trim_strings() {
         assert_eq!(trim_string("so 14 5"), "SO145");
         assert_eq!(trim_string("So 222"), "SO222");
         assert_eq!(trim_string(" @ @ "), "@@");
+        assert_eq!(trim_string("so-892-437"), "SO892437");
+        assert_eq!(trim_string("SO/892/437"), "SO892437");
+        assert_eq!(trim_string("SO_892.437"), "SO_892.437");
+    }
+
+    #[test]
+    fn calculates_distance() {
+        assert_eq!(distance(&Point::new(0.0, 0.0), &Point::new(3.0, 4.0)), 5.0);
+        assert_eq!(distance(&Point::new(3.0, 4.0), &Point::new(3.0, 4.0)), 0.0);
+    }
+
+    #[test]
+    fn polygon_contains_points() {
+        let square = LineString::from(vec![(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)]);
+        let hole = LineString::from(vec![(4.0, 4.0), (4.0, 6.0), (6.0, 6.0), (6.0, 4.0)]);
+        let polygon = Polygon::new(square, vec![hole]);
+
+        assert!(polygon_contains(&polygon, &Point::new(2.0, 2.0)));
+        assert!(!polygon_contains(&polygon, &Point::new(5.0, 5.0)));
+        assert!(!polygon_contains(&polygon, &Point::new(12.0, 2.0)));
+    }
+
+    #[test]
+    fn draws_line_cells() {
+        assert_eq!(line_cells((0, 0), (0, 0)), vec![(0, 0)]);
+        assert_eq!(
+            line_cells((0, 0), (3, 0)),
+            vec![(0, 0), (1, 0), (2, 0), (3, 0)]
+        );
+        assert_eq!(line_cells((2, 2), (0, 0)), vec![(2, 2), (1, 1), (0, 0)]);
+        assert_eq!(
+            line_cells((0, 0), (4, 2)),
+            vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]
+        );
+    }
+
+    #[test]
+    fn splits_span_across_tiles() {
+        assert_eq!(
+            axis_tiles(1_000.0, 2_000.0, 500.0, 1_000.0),
+            vec![(0, 500.0), (1, 500.0)]
+        );
+        assert_eq!(
+            axis_tiles(1_000.0, 2_000.0, 0.0, 1_000.0),
+            vec![(1, 1_000.0)]
+        );
+        assert_eq!(
+            axis_tiles(1_000.0, 2_000.0, 1_250.0, 500.0),
+            vec![(-1, 250.0), (0, 500.0), (1, 250.0)]
+        );
+    }
+
+    #[test]
+    fn draws_circle_cells() {
+        assert_eq!(circle_cells(0), vec![(0, 0)]);
+        assert_eq!(circle_cells(1), vec![(0, -1), (-1, 0), (1, 0), (0, 1)]);
+        assert_eq!(
+            circle_cells(2),
+            vec![
+                (-1, -2),
+                (0, -2),
+                (1, -2),
+                (-2, -1),
+                (2, -1),
+                (-2, 0),
+                (2, 0),
+                (-2, 1),
+                (2, 1),
+                (-1, 2),
+                (0, 2),
+                (1, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn walks_line_string_cells() {
+        assert!(line_string_cells(&LineString::new(vec![]), 10.0).is_empty());
+        assert_eq!(
+            line_string_cells(&LineString::from(vec![(15.0, 15.0)]), 10.0),
+            vec![(1, 1)]
+        );
+
+        // Straight, then turning back on itself.
+        let line = LineString::from(vec![(5.0, 5.0), (35.0, 5.0), (35.0, 25.0), (5.0, 25.0)]);
+        assert_eq!(
+            line_string_cells(&line, 10.0),
+            vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (3, 1),
+                (3, 2),
+                (2, 2),
+                (1, 2),
+                (0, 2)
+            ]
+        );
+
+        // A shallow diagonal crosses into each cell it touches.
+        let line = LineString::from(vec![(5.0, 5.0), (35.0, 15.0)]);
+        assert_eq!(
+            line_string_cells(&line, 10.0),
+            vec![(0, 0), (1, 0), (1, 1), (2, 1), (3, 1)]
+        );
+    }
+
+    #[test]
+    fn calculates_clipped_area() {
+        let rect = Rect::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 10.0, y: 10.0 });
+        let square = |min: f64, max: f64| {
+            LineString::from(vec![(min, min), (min, max), (max, max), (max, min)])
+        };
+
+        // Entirely within, entirely outside, and straddling the rect.
+        let within = Polygon::new(square(2.0, 8.0), vec![]);
+        assert_eq!(clipped_area(&within, &rect), 36.0);
+        let outside = Polygon::new(square(20.0, 30.0), vec![]);
+        assert_eq!(clipped_area(&outside, &rect), 0.0);
+        let straddling = Polygon::new(square(5.0, 15.0), vec![]);
+        assert_eq!(clipped_area(&straddling, &rect), 25.0);
+
+        // Holes are excluded.
+        let holed = Polygon::new(square(-5.0, 15.0), vec![square(4.0, 6.0)]);
+        assert_eq!(clipped_area(&holed, &rect), 96.0);
+
+        // A triangle covering half of the rect.
+        let triangle = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (0.0, 10.0), (10.0, 0.0)]),
+            vec![],
+        );
+        assert_eq!(clipped_area(&triangle, &rect), 50.0);
     }
 }
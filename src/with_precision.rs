@@ -0,0 +1,197 @@
+use crate::{Error, Precision, OSGB, OSI};
+use serde::{de, ser, ser::SerializeStruct};
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// A grid reference (de)serialized alongside its precision in metres,
+/// as `{ "ref": "SO892437", "precision_m": 100 }`, for consumers
+/// that do not parse grid reference strings themselves.
+///
+/// When deserializing, `ref` is parsed and must match `precision_m`.
+///
+/// # Example
+/// ```
+/// use gridish::{WithPrecision, OSGB};
+///
+/// let gridref: OSGB = "SO892437".parse().unwrap();
+/// let json = serde_json::to_string(&WithPrecision(gridref)).unwrap();
+///
+/// assert_eq!(json, r#"{"ref":"SO892437","precision_m":100}"#);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct WithPrecision<T>(pub T);
+
+/// Grid references that can be wrapped in [`WithPrecision`],
+/// implemented for both [`OSGB`] and [`OSI`].
+///
+/// Implementors are written with `Display` and read back with
+/// `FromStr`, and report their precision for the `precision_m` field.
+pub trait GridRef: Display + FromStr<Err = Error> {
+    /// Returns the grid reference's precision.
+    fn precision(&self) -> Precision;
+}
+
+impl GridRef for OSGB {
+    fn precision(&self) -> Precision {
+        self.precision()
+    }
+}
+
+impl GridRef for OSI {
+    fn precision(&self) -> Precision {
+        self.precision()
+    }
+}
+
+const FIELDS: &[&str] = &["ref", "precision_m"];
+
+impl<T: GridRef> ser::Serialize for WithPrecision<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut state = serializer.serialize_struct("WithPrecision", FIELDS.len())?;
+        state.serialize_field("ref", &self.0.to_string())?;
+        state.serialize_field("precision_m", &self.0.precision().metres())?;
+        state.end()
+    }
+}
+
+struct WithPrecisionVisitor<T>(PhantomData<T>);
+
+impl<'de, T: GridRef> de::Visitor<'de> for WithPrecisionVisitor<T> {
+    type Value = WithPrecision<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a grid ref string and its precision in metres")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let gridref: String = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let precision_m: u32 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        with_precision(gridref, precision_m)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut gridref: Option<String> = None;
+        let mut precision_m: Option<u32> = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "ref" if gridref.is_some() => return Err(de::Error::duplicate_field("ref")),
+                "ref" => gridref = Some(map.next_value()?),
+                "precision_m" if precision_m.is_some() => {
+                    return Err(de::Error::duplicate_field("precision_m"))
+                }
+                "precision_m" => precision_m = Some(map.next_value()?),
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            }
+        }
+
+        let gridref = gridref.ok_or_else(|| de::Error::missing_field("ref"))?;
+        let precision_m = precision_m.ok_or_else(|| de::Error::missing_field("precision_m"))?;
+
+        with_precision(gridref, precision_m)
+    }
+}
+
+/// Parses the grid reference, checking it matches the given precision.
+fn with_precision<T: GridRef, E: de::Error>(
+    gridref: String,
+    precision_m: u32,
+) -> Result<WithPrecision<T>, E> {
+    let gridref: T = gridref.parse().map_err(de::Error::custom)?;
+
+    if gridref.precision().metres() != precision_m {
+        return Err(de::Error::custom(Error::InvalidPrecision(format!(
+            "Expected precision of {}m, found {}m.",
+            precision_m,
+            gridref.precision().metres()
+        ))));
+    }
+
+    Ok(WithPrecision(gridref))
+}
+
+impl<'de, T: GridRef> de::Deserialize<'de> for WithPrecision<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("WithPrecision", FIELDS, WithPrecisionVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{WithPrecision, OSGB, OSI};
+
+    #[test]
+    fn round_trips_osgb_with_precision() {
+        let gridref: OSGB = "SO892437".parse().unwrap();
+        let json = serde_json::to_string(&WithPrecision(gridref.clone())).unwrap();
+
+        assert_eq!(json, r#"{"ref":"SO892437","precision_m":100}"#);
+        assert_eq!(
+            serde_json::from_str::<WithPrecision<OSGB>>(&json).unwrap(),
+            WithPrecision(gridref)
+        );
+    }
+
+    #[test]
+    fn round_trips_osi_with_precision() {
+        let gridref: OSI = "O8943".parse().unwrap();
+        let json = serde_json::to_string(&WithPrecision(gridref.clone())).unwrap();
+
+        assert_eq!(json, r#"{"ref":"O8943","precision_m":1000}"#);
+        assert_eq!(
+            serde_json::from_str::<WithPrecision<OSI>>(&json).unwrap(),
+            WithPrecision(gridref)
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_precision() {
+        let json = r#"{"ref":"SO892437","precision_m":1000}"#;
+        let error = serde_json::from_str::<WithPrecision<OSGB>>(json).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("Invalid precision: Expected precision of 1000m, found 100m."));
+        assert!(serde_json::from_str::<WithPrecision<OSI>>(r#"{"ref":"O8943"}"#).is_err());
+    }
+
+    #[test]
+    fn deserializes_from_sequence() {
+        let gridref = serde_json::from_str::<WithPrecision<OSGB>>(r#"["SO892437",100]"#).unwrap();
+        assert_eq!(gridref, WithPrecision("SO892437".parse().unwrap()));
+
+        assert!(serde_json::from_str::<WithPrecision<OSI>>(r#"["O8943",100]"#).is_err());
+        assert!(serde_json::from_str::<WithPrecision<OSI>>(r#"["O8943"]"#).is_err());
+    }
+
+    #[test]
+    fn rejects_duplicate_fields() {
+        let json = r#"{"ref":"SO892437","ref":"SO8943","precision_m":1000}"#;
+        let error = serde_json::from_str::<WithPrecision<OSGB>>(json).unwrap_err();
+        assert!(error.to_string().starts_with("duplicate field `ref`"));
+
+        let json = r#"{"ref":"O8943","precision_m":1000,"precision_m":1000}"#;
+        let error = serde_json::from_str::<WithPrecision<OSI>>(json).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("duplicate field `precision_m`"));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::data::osgb_grids;
-use gridish::{Error, OSGB};
+use geo_types::{Geometry, LineString, MultiPolygon, Point, Polygon};
+use gridish::{Anchor, Error, GridSystem, Precision, OSGB};
 
 #[test]
 fn parses_valid_strings() {
@@ -23,6 +24,13 @@ fn rejects_invalid_strings() {
         ))
     );
 
+    assert_eq!(
+        "SO 892437 892437".parse::<OSGB>(),
+        Err(Error::ParseError(
+            "12 digits exceeds the maximum of 10 significant digits (1m precision).".to_string()
+        ))
+    );
+
     assert_eq!(
         "123".parse::<OSGB>(),
         Err(Error::ParseError(
@@ -30,6 +38,13 @@ fn rejects_invalid_strings() {
         ))
     );
 
+    assert_eq!(
+        "S".parse::<OSGB>(),
+        Err(Error::ParseError(
+            "S is missing a 100km square.".to_string()
+        ))
+    );
+
     assert_eq!(
         "T45".parse::<OSGB>(),
         Err(Error::ParseError(
@@ -46,5 +61,526 @@ fn prints_correct_strings() {
         let grid = OSGB::new(item.eastings, item.northings, item.precision).unwrap();
 
         assert_eq!(item.output_string, grid.to_string());
+        assert_eq!(item.output_string.len(), grid.display_len());
+    }
+}
+
+#[test]
+fn parses_expected_precision() {
+    let grid = OSGB::parse_expecting("SO892437", Precision::_100M).unwrap();
+    assert_eq!(grid.precision(), Precision::_100M);
+
+    assert_eq!(
+        OSGB::parse_expecting("SO8943", Precision::_100M),
+        Err(Error::InvalidPrecision(
+            "Expected precision _100M, found _1Km.".to_string()
+        ))
+    );
+}
+
+#[test]
+fn detects_canonical_strings() {
+    assert!(OSGB::is_canonical("NS2468"));
+    assert!(OSGB::is_canonical(" NS2468 "));
+    assert!(!OSGB::is_canonical("ns 24 68"));
+    assert!(!OSGB::is_canonical("ns2468"));
+    assert!(!OSGB::is_canonical("not a gridref"));
+}
+
+#[test]
+fn creates_from_absolute_strings() {
+    let grid = OSGB::from_absolute("389200", "243700", Precision::_100M).unwrap();
+    assert_eq!(grid.to_string(), "SO892437");
+
+    let grid = OSGB::from_absolute("0389291", "0243762", Precision::_1M).unwrap();
+    assert_eq!(grid.to_string(), "SO8929143762");
+
+    assert_eq!(
+        OSGB::from_absolute("389200", "2000000", Precision::_100M),
+        Err(Error::OutOfBounds)
+    );
+
+    assert_eq!(
+        OSGB::from_absolute("389200", "-243700", Precision::_100M),
+        Err(Error::ParseError(
+            "ParseIntError { kind: InvalidDigit }".to_string()
+        ))
+    );
+}
+
+#[test]
+fn parses_separated_strings() {
+    for s in ["SO-892-437", "SO/892/437", "SO 892-437"] {
+        let grid: OSGB = s.parse().unwrap();
+
+        assert_eq!(grid.to_string(), "SO892437");
+    }
+
+    assert!("SO_892_437".parse::<OSGB>().is_err());
+}
+
+#[test]
+fn converts_from_strings() {
+    let grid = OSGB::try_from("SO892437").unwrap();
+    assert_eq!(grid.to_string(), "SO892437");
+
+    let grid = OSGB::try_from(" so892437 ".to_string()).unwrap();
+    assert_eq!(grid.to_string(), "SO892437");
+
+    let grid: Result<OSGB, Error> = "123".try_into();
+    assert_eq!(
+        grid,
+        Err(Error::ParseError(
+            "1 is not a valid grid square.".to_string()
+        ))
+    );
+}
+
+#[test]
+fn parses_unpadded_groups() {
+    let grid = OSGB::parse_unpadded("892", "437", "SO").unwrap();
+    assert_eq!(grid.to_string(), "SO892437");
+
+    let grid = OSGB::parse_unpadded(" 8 ", " 4 ", "so").unwrap();
+    assert_eq!(grid.to_string(), "SO84");
+
+    assert_eq!(
+        OSGB::parse_unpadded("892", "43", "SO"),
+        Err(Error::InvalidPrecision(
+            "Eastings imply _100M but northings imply _1Km.".to_string()
+        ))
+    );
+
+    assert_eq!(
+        OSGB::parse_unpadded("892", "437", "SO12"),
+        Err(Error::ParseError(
+            "SO12 is not a valid 100km square.".to_string()
+        ))
+    );
+
+    // A sign is not a digit, and must not pad the group.
+    assert_eq!(
+        OSGB::parse_unpadded("+12", "437", "SO"),
+        Err(Error::ParseError("+ is not a valid digit.".to_string()))
+    );
+}
+
+#[test]
+fn partitions_parsed_strings() {
+    let inputs = ["SO892437", "123", "ns 24 68", ""].map(|s| s.to_string());
+    let (grids, failures) = OSGB::partition_parse(inputs);
+
+    let grids: Vec<String> = grids.iter().map(|grid| grid.to_string()).collect();
+    assert_eq!(grids, vec!["SO892437", "NS2468"]);
+
+    assert_eq!(
+        failures,
+        vec![
+            (
+                "123".to_string(),
+                Error::ParseError("1 is not a valid grid square.".to_string())
+            ),
+            ("".to_string(), Error::Empty),
+        ]
+    );
+}
+
+#[test]
+fn rejects_empty_strings() {
+    for s in ["", "   ", " - / "] {
+        assert_eq!(s.parse::<OSGB>(), Err(Error::Empty));
+    }
+}
+
+#[test]
+fn reports_grid_system() {
+    let gridref: OSGB = "OV892437".parse().unwrap();
+
+    assert_eq!(gridref.grid_system(), GridSystem::Osgb);
+}
+
+#[test]
+fn label_anchor_matches_corner_methods() {
+    let gridref: OSGB = "SO892437".parse().unwrap();
+
+    assert_eq!(gridref.label_anchor(Anchor::SW), gridref.sw());
+    assert_eq!(gridref.label_anchor(Anchor::NW), gridref.nw());
+    assert_eq!(gridref.label_anchor(Anchor::NE), gridref.ne());
+    assert_eq!(gridref.label_anchor(Anchor::SE), gridref.se());
+    assert_eq!(gridref.label_anchor(Anchor::Centre), gridref.centre());
+}
+
+#[test]
+fn formats_into_exact_buffer() {
+    let gridref: OSGB = "SO892437".parse().unwrap();
+    let mut buffer = [0; 8];
+
+    assert_eq!(gridref.format_into(&mut buffer), Ok(8));
+    assert_eq!(OSGB::parse_ascii(&buffer), Ok(gridref));
+}
+
+#[test]
+fn rejects_too_small_buffer() {
+    let gridref: OSGB = "SO892437".parse().unwrap();
+    let mut buffer = [0; 7];
+
+    assert_eq!(
+        gridref.format_into(&mut buffer),
+        Err(Error::BufferTooSmall(8))
+    );
+}
+
+#[test]
+fn rejects_invalid_ascii() {
+    assert!(matches!(
+        OSGB::parse_ascii(&[0xff, 0xfe]),
+        Err(Error::ParseError(_))
+    ));
+}
+
+#[test]
+fn parses_at_coarser_precision() {
+    assert_eq!(
+        OSGB::parse_at("SO892437", Precision::_1Km)
+            .unwrap()
+            .to_string(),
+        "SO8943"
+    );
+    assert_eq!(
+        OSGB::parse_at("SO892437", Precision::_100M)
+            .unwrap()
+            .to_string(),
+        "SO892437"
+    );
+    assert_eq!(
+        OSGB::parse_at("SO8943", Precision::_10M)
+            .unwrap()
+            .to_string(),
+        "SO8943"
+    );
+    assert!(OSGB::parse_at("SO89@", Precision::_1Km).is_err());
+}
+
+#[test]
+fn dimensions_match_precision() {
+    for s in [
+        "SO",
+        "SO84",
+        "SO8943",
+        "SO892437",
+        "SO89294376",
+        "SO8929143762",
+    ] {
+        let gridref: OSGB = s.parse().unwrap();
+        let metres = gridref.precision().metres();
+
+        assert_eq!(gridref.dimensions(), (metres, metres));
+    }
+}
+
+#[test]
+fn parses_max_precision() {
+    assert!(OSGB::parse_max_precision("SO8943", Precision::_1Km).is_ok());
+    assert!(OSGB::parse_max_precision("SO84", Precision::_1Km).is_ok());
+    assert_eq!(
+        OSGB::parse_max_precision("SO892437", Precision::_1Km),
+        Err(Error::InvalidPrecision(
+            "Expected precision no finer than _1Km, found _100M.".to_string()
+        ))
+    );
+    assert!(matches!(
+        OSGB::parse_max_precision("SO89@", Precision::_1Km),
+        Err(Error::ParseError(_))
+    ));
+}
+
+#[test]
+fn parses_in_allowed_squares() {
+    let allowed = ["SO", "SP"];
+
+    assert_eq!(
+        OSGB::parse_in_squares("SO892437", &allowed)
+            .unwrap()
+            .to_string(),
+        "SO892437"
+    );
+    assert!(OSGB::parse_in_squares("SP1234", &["sp"]).is_ok());
+    assert_eq!(
+        OSGB::parse_in_squares("SN892437", &allowed),
+        Err(Error::ParseError(
+            "SN is not an allowed grid square.".to_string()
+        ))
+    );
+    assert!(OSGB::parse_in_squares("SO892437", &[]).is_err());
+}
+
+#[test]
+fn formats_teaching_form() {
+    for (s, expected) in [
+        ("SO892437", "03,02 892437"),
+        ("SO8943", "03,02 8943"),
+        ("SO", "03,02"),
+        ("HP", "04,12"),
+    ] {
+        let gridref: OSGB = s.parse().unwrap();
+
+        assert_eq!(gridref.to_teaching_form(), expected);
+    }
+}
+
+#[test]
+fn creates_from_pixel() {
+    let origin = (389_000.0, 244_000.0);
+
+    // The top left pixel lies just below the origin's northings.
+    let gridref = OSGB::from_pixel(0.0, 0.5, origin, 10.0, Precision::_1Km).unwrap();
+    assert_eq!(gridref.to_string(), "SO8943");
+    let gridref = OSGB::from_pixel(0.0, 0.0, origin, 10.0, Precision::_1Km).unwrap();
+    assert_eq!(gridref.to_string(), "SO8944");
+
+    let gridref = OSGB::from_pixel(25.0, 5.0, origin, 10.0, Precision::_100M).unwrap();
+    assert_eq!(gridref.to_string(), "SO892439");
+
+    assert_eq!(
+        OSGB::from_pixel(-1.0, 0.0, (0.0, 0.0), 1.0, Precision::_1Km),
+        Err(Error::OutOfBounds)
+    );
+    assert_eq!(
+        OSGB::from_pixel(0.0, 0.0, origin, f64::NAN, Precision::_1Km),
+        Err(Error::OutOfBounds)
+    );
+}
+
+#[test]
+fn shares_square_ref() {
+    let square: OSGB = "SO".parse().unwrap();
+
+    for s in ["SO84", "SO8943", "SO892437", "SO0000", "SO9999999999"] {
+        let gridref: OSGB = s.parse().unwrap();
+
+        assert_eq!(gridref.square_ref(), square);
+    }
+}
+
+#[test]
+fn converts_to_point_geometry() {
+    let gridref: OSGB = "SO892437".parse().unwrap();
+
+    assert_eq!(
+        gridref.to_point_geometry(Anchor::Centre),
+        Geometry::Point(gridref.centre())
+    );
+    assert_eq!(
+        gridref.to_point_geometry(Anchor::NE),
+        Geometry::Point(gridref.ne())
+    );
+}
+
+#[test]
+fn round_trips_km_index() {
+    let gridref = OSGB::from_km_index(389, 243).unwrap();
+
+    assert_eq!(gridref.to_string(), "SO8943");
+    assert_eq!(gridref.precision(), Precision::_1Km);
+    assert_eq!(gridref.km_index(), (389, 243));
+    assert_eq!(OSGB::from_km_index(0, 0).unwrap().km_index(), (0, 0));
+    assert_eq!(OSGB::from_km_index(u32::MAX, 0), Err(Error::OutOfBounds));
+}
+
+#[test]
+fn rejects_unequal_digit_groups() {
+    assert_eq!(
+        OSGB::from_str_checked("SO84 37").unwrap().to_string(),
+        "SO8437"
+    );
+    assert_eq!(
+        OSGB::from_str_checked("SO 892 437").unwrap().to_string(),
+        "SO892437"
+    );
+    assert!(matches!(
+        OSGB::from_str_checked("SO8 437"),
+        Err(Error::ParseError(_))
+    ));
+    // Three groups are as ambiguous as two unequal ones.
+    assert!(matches!(
+        OSGB::from_str_checked("SO8 43 7"),
+        Err(Error::ParseError(_))
+    ));
+    assert!("SO8 43 7".parse::<OSGB>().is_ok());
+
+    // The default parser accepts it.
+    assert!("SO8 437".parse::<OSGB>().is_ok());
+}
+
+#[test]
+fn antipode_maps_sw_to_ne() {
+    let gridref: OSGB = "TV012034".parse().unwrap();
+    let antipode = gridref.antipode().unwrap();
+
+    assert_eq!(antipode.to_string(), "HE987965");
+    assert_eq!(antipode.precision(), gridref.precision());
+    assert_eq!(antipode.antipode().unwrap(), gridref);
+
+    // The antipode of SV lies in the invalid J square.
+    let corner: OSGB = "SV0000".parse().unwrap();
+    assert!(corner.antipode().is_none());
+}
+
+#[test]
+fn cells_on_circle_form_ring() {
+    let centre = Point::new(389_500.0, 243_500.0);
+    let cells = OSGB::cells_on_circle(centre, 2_000.0, Precision::_1Km).unwrap();
+    let cells: Vec<String> = cells.iter().map(|cell| cell.to_string()).collect();
+
+    assert_eq!(
+        cells,
+        vec![
+            "SO8841", "SO8941", "SO9041", "SO8742", "SO9142", "SO8743", "SO9143", "SO8744",
+            "SO9144", "SO8845", "SO8945", "SO9045",
+        ]
+    );
+
+    // A zero radius leaves just the centre's cell.
+    let cells = OSGB::cells_on_circle(centre, 0.0, Precision::_1Km).unwrap();
+    assert_eq!(cells, vec!["SO8943".parse().unwrap()]);
+
+    assert!(OSGB::cells_on_circle(centre, -1.0, Precision::_1Km).is_err());
+    assert!(OSGB::cells_on_circle(Point::new(500.0, 500.0), 2_000.0, Precision::_1Km).is_err());
+}
+
+#[test]
+fn tile_overlaps_straddle_four_tiles() {
+    let gridref: OSGB = "SO8943".parse().unwrap();
+    let overlaps = gridref.tile_overlaps((389_250.0, 243_750.0), 1_000.0);
+
+    assert_eq!(
+        overlaps,
+        vec![
+            (-1, -1, 187_500.0),
+            (0, -1, 562_500.0),
+            (-1, 0, 62_500.0),
+            (0, 0, 187_500.0),
+        ]
+    );
+    let total: f64 = overlaps.iter().map(|(_, _, area)| area).sum();
+    assert_eq!(total, 1_000_000.0);
+
+    // Aligned tiles of the same size overlap the cell exactly.
+    assert_eq!(
+        gridref.tile_overlaps((0.0, 0.0), 1_000.0),
+        vec![(389, 243, 1_000_000.0)]
+    );
+    assert!(gridref.tile_overlaps((0.0, 0.0), 0.0).is_empty());
+}
+
+#[test]
+fn round_trips_check_character() {
+    for item in osgb_grids() {
+        let gridref: OSGB = item.input_string.parse().unwrap();
+        let checked = gridref.to_string_with_check();
+
+        assert_eq!(checked.len(), gridref.to_string().len() + 1);
+        assert_eq!(OSGB::from_string_with_check(&checked).unwrap(), gridref);
     }
+
+    let gridref: OSGB = "SO892437".parse().unwrap();
+    assert_eq!(gridref.to_string_with_check(), "SO892437E");
+    assert_eq!(
+        OSGB::from_string_with_check("so 892 437 e").unwrap(),
+        gridref
+    );
+}
+
+#[test]
+fn rejects_corrupted_check_character() {
+    // Transposed digits.
+    assert!(matches!(
+        OSGB::from_string_with_check("SO892473E"),
+        Err(Error::ParseError(_))
+    ));
+    // Mistyped check character.
+    assert!(matches!(
+        OSGB::from_string_with_check("SO892437Z"),
+        Err(Error::ParseError(_))
+    ));
+    assert_eq!(OSGB::from_string_with_check(""), Err(Error::Empty));
+}
+
+#[test]
+fn divergence_precision_finds_finest_shared_cell() {
+    let gridref: OSGB = "SO892437".parse().unwrap();
+
+    // Agreeing at 10km, but differing at 1km.
+    let other: OSGB = "SO815478".parse().unwrap();
+    assert_eq!(gridref.divergence_precision(&other), Some(Precision::_10Km));
+    assert_ne!(
+        gridref.recalculate(Precision::_1Km),
+        other.recalculate(Precision::_1Km)
+    );
+
+    // Limited by the coarser of the two.
+    let other: OSGB = "SO8943".parse().unwrap();
+    assert_eq!(gridref.divergence_precision(&other), Some(Precision::_1Km));
+    assert_eq!(
+        gridref.divergence_precision(&gridref),
+        Some(Precision::_100M)
+    );
+
+    let other: OSGB = "SP892437".parse().unwrap();
+    assert_eq!(gridref.divergence_precision(&other), None);
+}
+
+#[test]
+fn cells_in_multipolygon_cover_both_parts() {
+    let part = |x: f64, y: f64, size: f64| {
+        Polygon::new(
+            LineString::from(vec![
+                (x, y),
+                (x + size, y),
+                (x + size, y + size),
+                (x, y + size),
+            ]),
+            vec![],
+        )
+    };
+    let area = MultiPolygon::new(vec![
+        part(389_000.0, 243_000.0, 2_000.0),
+        part(395_200.0, 245_200.0, 1_000.0),
+    ]);
+
+    let cells = OSGB::cells_in_multipolygon(&area, Precision::_1Km).unwrap();
+    let cells: Vec<String> = cells.iter().map(|cell| cell.to_string()).collect();
+    assert_eq!(
+        cells,
+        vec!["SO8943", "SO9043", "SO8944", "SO9044", "SO9545"]
+    );
+
+    assert!(
+        OSGB::cells_in_multipolygon(&MultiPolygon::new(vec![]), Precision::_1Km)
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        OSGB::cells_in_multipolygon(
+            &MultiPolygon::new(vec![part(-500.0, 0.0, 1_000.0)]),
+            Precision::_1Km
+        ),
+        Err(Error::OutOfBounds)
+    );
+}
+
+#[test]
+fn ring_closed_repeats_first_coordinate() {
+    let gridref: OSGB = "SO892437".parse().unwrap();
+    let ring = gridref.ring_closed();
+
+    assert!(ring.is_closed());
+    assert_eq!(ring.0.len(), 5);
+    assert_eq!(ring.0.first(), ring.0.last());
+    assert_eq!(ring.0[0], gridref.sw().into());
+
+    // Closing the ring leaves the area unchanged.
+    let polygon = Polygon::new(ring, vec![]);
+    assert_eq!(polygon, gridref.perimeter());
+    assert_eq!(gridref.coverage_fraction(&polygon), 1.0);
 }
@@ -1,5 +1,6 @@
 use crate::data::osi_grids;
-use gridish::{Error, OSI};
+use geo_types::{Geometry, LineString, MultiPolygon, Point, Polygon};
+use gridish::{Anchor, Error, GridSystem, Precision, OSI};
 
 #[test]
 fn parses_valid_strings() {
@@ -39,5 +40,507 @@ fn prints_correct_strings() {
         let grid = OSI::new(item.eastings, item.northings, item.precision).unwrap();
 
         assert_eq!(item.output_string, grid.to_string());
+        assert_eq!(item.output_string.len(), grid.display_len());
     }
 }
+
+#[test]
+fn parses_expected_precision() {
+    let grid = OSI::parse_expecting("O892437", Precision::_100M).unwrap();
+    assert_eq!(grid.precision(), Precision::_100M);
+
+    assert_eq!(
+        OSI::parse_expecting("O8943", Precision::_100M),
+        Err(Error::InvalidPrecision(
+            "Expected precision _100M, found _1Km.".to_string()
+        ))
+    );
+}
+
+#[test]
+fn detects_canonical_strings() {
+    assert!(OSI::is_canonical("S2468"));
+    assert!(OSI::is_canonical(" S2468 "));
+    assert!(!OSI::is_canonical("s 24 68"));
+    assert!(!OSI::is_canonical("s2468"));
+    assert!(!OSI::is_canonical("not a gridref"));
+}
+
+#[test]
+fn creates_from_absolute_strings() {
+    let grid = OSI::from_absolute("389200", "243700", Precision::_100M).unwrap();
+    assert_eq!(grid.to_string(), "O892437");
+
+    let grid = OSI::from_absolute("0389291", "0243762", Precision::_1M).unwrap();
+    assert_eq!(grid.to_string(), "O8929143762");
+
+    assert_eq!(
+        OSI::from_absolute("389200", "2000000", Precision::_100M),
+        Err(Error::OutOfBounds)
+    );
+
+    assert_eq!(
+        OSI::from_absolute("389200", "-243700", Precision::_100M),
+        Err(Error::ParseError(
+            "ParseIntError { kind: InvalidDigit }".to_string()
+        ))
+    );
+}
+
+#[test]
+fn parses_separated_strings() {
+    for s in ["O-892-437", "O/892/437", "O 892-437"] {
+        let grid: OSI = s.parse().unwrap();
+
+        assert_eq!(grid.to_string(), "O892437");
+    }
+
+    assert!("O_892_437".parse::<OSI>().is_err());
+}
+
+#[test]
+fn converts_from_strings() {
+    let grid = OSI::try_from("O892437").unwrap();
+    assert_eq!(grid.to_string(), "O892437");
+
+    let grid = OSI::try_from(" o892437 ".to_string()).unwrap();
+    assert_eq!(grid.to_string(), "O892437");
+
+    let grid: Result<OSI, Error> = "123".try_into();
+    assert_eq!(
+        grid,
+        Err(Error::ParseError(
+            "1 is not a valid grid square.".to_string()
+        ))
+    );
+}
+
+#[test]
+fn parses_unpadded_groups() {
+    let grid = OSI::parse_unpadded("892", "437", "O").unwrap();
+    assert_eq!(grid.to_string(), "O892437");
+
+    let grid = OSI::parse_unpadded(" 8 ", " 4 ", "o").unwrap();
+    assert_eq!(grid.to_string(), "O84");
+
+    assert_eq!(
+        OSI::parse_unpadded("892", "43", "O"),
+        Err(Error::InvalidPrecision(
+            "Eastings imply _100M but northings imply _1Km.".to_string()
+        ))
+    );
+
+    assert_eq!(
+        OSI::parse_unpadded("892", "437", "O12"),
+        Err(Error::ParseError(
+            "O12 is not a valid 100km square.".to_string()
+        ))
+    );
+
+    // A sign is not a digit, and must not pad the group.
+    assert_eq!(
+        OSI::parse_unpadded("+12", "437", "O"),
+        Err(Error::ParseError("+ is not a valid digit.".to_string()))
+    );
+}
+
+#[test]
+fn partitions_parsed_strings() {
+    let inputs = ["O892437", "123", "s 24 68", ""].map(|s| s.to_string());
+    let (grids, failures) = OSI::partition_parse(inputs);
+
+    let grids: Vec<String> = grids.iter().map(|grid| grid.to_string()).collect();
+    assert_eq!(grids, vec!["O892437", "S2468"]);
+
+    assert_eq!(
+        failures,
+        vec![
+            (
+                "123".to_string(),
+                Error::ParseError("1 is not a valid grid square.".to_string())
+            ),
+            ("".to_string(), Error::Empty),
+        ]
+    );
+}
+
+#[test]
+fn rejects_empty_strings() {
+    for s in ["", "   ", " - / "] {
+        assert_eq!(s.parse::<OSI>(), Err(Error::Empty));
+    }
+}
+
+#[test]
+fn reports_grid_system() {
+    let gridref: OSI = "O892437".parse().unwrap();
+
+    assert_eq!(gridref.grid_system(), GridSystem::Osi);
+}
+
+#[test]
+fn label_anchor_matches_corner_methods() {
+    let gridref: OSI = "O892437".parse().unwrap();
+
+    assert_eq!(gridref.label_anchor(Anchor::SW), gridref.sw());
+    assert_eq!(gridref.label_anchor(Anchor::NW), gridref.nw());
+    assert_eq!(gridref.label_anchor(Anchor::NE), gridref.ne());
+    assert_eq!(gridref.label_anchor(Anchor::SE), gridref.se());
+    assert_eq!(gridref.label_anchor(Anchor::Centre), gridref.centre());
+}
+
+#[test]
+fn formats_into_exact_buffer() {
+    let gridref: OSI = "O892437".parse().unwrap();
+    let mut buffer = [0; 7];
+
+    assert_eq!(gridref.format_into(&mut buffer), Ok(7));
+    assert_eq!(OSI::parse_ascii(&buffer), Ok(gridref));
+}
+
+#[test]
+fn rejects_too_small_buffer() {
+    let gridref: OSI = "O892437".parse().unwrap();
+    let mut buffer = [0; 6];
+
+    assert_eq!(
+        gridref.format_into(&mut buffer),
+        Err(Error::BufferTooSmall(7))
+    );
+}
+
+#[test]
+fn rejects_invalid_ascii() {
+    assert!(matches!(
+        OSI::parse_ascii(&[0xff, 0xfe]),
+        Err(Error::ParseError(_))
+    ));
+}
+
+#[test]
+fn parses_at_coarser_precision() {
+    assert_eq!(
+        OSI::parse_at("O892437", Precision::_1Km)
+            .unwrap()
+            .to_string(),
+        "O8943"
+    );
+    assert_eq!(
+        OSI::parse_at("O892437", Precision::_100M)
+            .unwrap()
+            .to_string(),
+        "O892437"
+    );
+    assert_eq!(
+        OSI::parse_at("O8943", Precision::_10M).unwrap().to_string(),
+        "O8943"
+    );
+    assert!(OSI::parse_at("O89@", Precision::_1Km).is_err());
+}
+
+#[test]
+fn dimensions_match_precision() {
+    for s in ["O", "O84", "O8943", "O892437", "O89294376", "O8929143762"] {
+        let gridref: OSI = s.parse().unwrap();
+        let metres = gridref.precision().metres();
+
+        assert_eq!(gridref.dimensions(), (metres, metres));
+    }
+}
+
+#[test]
+fn parses_max_precision() {
+    assert!(OSI::parse_max_precision("O8943", Precision::_1Km).is_ok());
+    assert!(OSI::parse_max_precision("O84", Precision::_1Km).is_ok());
+    assert_eq!(
+        OSI::parse_max_precision("O892437", Precision::_1Km),
+        Err(Error::InvalidPrecision(
+            "Expected precision no finer than _1Km, found _100M.".to_string()
+        ))
+    );
+    assert!(matches!(
+        OSI::parse_max_precision("O89@", Precision::_1Km),
+        Err(Error::ParseError(_))
+    ));
+}
+
+#[test]
+fn parses_in_allowed_squares() {
+    let allowed = ["O", "N"];
+
+    assert_eq!(
+        OSI::parse_in_squares("O892437", &allowed)
+            .unwrap()
+            .to_string(),
+        "O892437"
+    );
+    assert!(OSI::parse_in_squares("N1234", &["n"]).is_ok());
+    assert_eq!(
+        OSI::parse_in_squares("M892437", &allowed),
+        Err(Error::ParseError(
+            "M is not an allowed grid square.".to_string()
+        ))
+    );
+    assert!(OSI::parse_in_squares("O892437", &[]).is_err());
+}
+
+#[test]
+fn formats_teaching_form() {
+    for (s, expected) in [
+        ("O892437", "03,02 892437"),
+        ("O8943", "03,02 8943"),
+        ("O", "03,02"),
+        ("A", "00,04"),
+    ] {
+        let gridref: OSI = s.parse().unwrap();
+
+        assert_eq!(gridref.to_teaching_form(), expected);
+    }
+}
+
+#[test]
+fn creates_from_pixel() {
+    let origin = (389_000.0, 244_000.0);
+
+    // The top left pixel lies just below the origin's northings.
+    let gridref = OSI::from_pixel(0.0, 0.5, origin, 10.0, Precision::_1Km).unwrap();
+    assert_eq!(gridref.to_string(), "O8943");
+    let gridref = OSI::from_pixel(0.0, 0.0, origin, 10.0, Precision::_1Km).unwrap();
+    assert_eq!(gridref.to_string(), "O8944");
+
+    let gridref = OSI::from_pixel(25.0, 5.0, origin, 10.0, Precision::_100M).unwrap();
+    assert_eq!(gridref.to_string(), "O892439");
+
+    assert_eq!(
+        OSI::from_pixel(-1.0, 0.0, (0.0, 0.0), 1.0, Precision::_1Km),
+        Err(Error::OutOfBounds)
+    );
+    assert_eq!(
+        OSI::from_pixel(0.0, 0.0, origin, f64::NAN, Precision::_1Km),
+        Err(Error::OutOfBounds)
+    );
+}
+
+#[test]
+fn shares_square_ref() {
+    let square: OSI = "O".parse().unwrap();
+
+    for s in ["O84", "O8943", "O892437", "O0000", "O9999999999"] {
+        let gridref: OSI = s.parse().unwrap();
+
+        assert_eq!(gridref.square_ref(), square);
+    }
+}
+
+#[test]
+fn converts_to_point_geometry() {
+    let gridref: OSI = "O892437".parse().unwrap();
+
+    assert_eq!(
+        gridref.to_point_geometry(Anchor::Centre),
+        Geometry::Point(gridref.centre())
+    );
+    assert_eq!(
+        gridref.to_point_geometry(Anchor::NE),
+        Geometry::Point(gridref.ne())
+    );
+}
+
+#[test]
+fn round_trips_km_index() {
+    let gridref = OSI::from_km_index(389, 243).unwrap();
+
+    assert_eq!(gridref.to_string(), "O8943");
+    assert_eq!(gridref.precision(), Precision::_1Km);
+    assert_eq!(gridref.km_index(), (389, 243));
+    assert_eq!(OSI::from_km_index(0, 0).unwrap().km_index(), (0, 0));
+    assert_eq!(OSI::from_km_index(u32::MAX, 0), Err(Error::OutOfBounds));
+}
+
+#[test]
+fn rejects_unequal_digit_groups() {
+    assert_eq!(
+        OSI::from_str_checked("O84 37").unwrap().to_string(),
+        "O8437"
+    );
+    assert_eq!(
+        OSI::from_str_checked("O 892 437").unwrap().to_string(),
+        "O892437"
+    );
+    assert!(matches!(
+        OSI::from_str_checked("O8 437"),
+        Err(Error::ParseError(_))
+    ));
+    // Three groups are as ambiguous as two unequal ones.
+    assert!(matches!(
+        OSI::from_str_checked("O8 43 7"),
+        Err(Error::ParseError(_))
+    ));
+    assert!("O8 43 7".parse::<OSI>().is_ok());
+
+    // The default parser accepts it.
+    assert!("O8 437".parse::<OSI>().is_ok());
+}
+
+#[test]
+fn antipode_maps_sw_to_ne() {
+    let gridref: OSI = "V012034".parse().unwrap();
+    let antipode = gridref.antipode().unwrap();
+
+    assert_eq!(antipode.to_string(), "E987965");
+    assert_eq!(antipode.precision(), gridref.precision());
+    assert_eq!(antipode.antipode().unwrap(), gridref);
+}
+
+#[test]
+fn cells_on_circle_form_ring() {
+    let centre = Point::new(389_500.0, 243_500.0);
+    let cells = OSI::cells_on_circle(centre, 2_000.0, Precision::_1Km).unwrap();
+    let cells: Vec<String> = cells.iter().map(|cell| cell.to_string()).collect();
+
+    assert_eq!(
+        cells,
+        vec![
+            "O8841", "O8941", "O9041", "O8742", "O9142", "O8743", "O9143", "O8744", "O9144",
+            "O8845", "O8945", "O9045",
+        ]
+    );
+
+    // A zero radius leaves just the centre's cell.
+    let cells = OSI::cells_on_circle(centre, 0.0, Precision::_1Km).unwrap();
+    assert_eq!(cells, vec!["O8943".parse().unwrap()]);
+
+    assert!(OSI::cells_on_circle(centre, -1.0, Precision::_1Km).is_err());
+    assert!(OSI::cells_on_circle(Point::new(500.0, 500.0), 2_000.0, Precision::_1Km).is_err());
+}
+
+#[test]
+fn tile_overlaps_straddle_four_tiles() {
+    let gridref: OSI = "O8943".parse().unwrap();
+    let overlaps = gridref.tile_overlaps((389_250.0, 243_750.0), 1_000.0);
+
+    assert_eq!(
+        overlaps,
+        vec![
+            (-1, -1, 187_500.0),
+            (0, -1, 562_500.0),
+            (-1, 0, 62_500.0),
+            (0, 0, 187_500.0),
+        ]
+    );
+    let total: f64 = overlaps.iter().map(|(_, _, area)| area).sum();
+    assert_eq!(total, 1_000_000.0);
+
+    // Aligned tiles of the same size overlap the cell exactly.
+    assert_eq!(
+        gridref.tile_overlaps((0.0, 0.0), 1_000.0),
+        vec![(389, 243, 1_000_000.0)]
+    );
+    assert!(gridref.tile_overlaps((0.0, 0.0), 0.0).is_empty());
+}
+
+#[test]
+fn round_trips_check_character() {
+    for item in osi_grids() {
+        let gridref: OSI = item.input_string.parse().unwrap();
+        let checked = gridref.to_string_with_check();
+
+        assert_eq!(checked.len(), gridref.to_string().len() + 1);
+        assert_eq!(OSI::from_string_with_check(&checked).unwrap(), gridref);
+    }
+
+    let gridref: OSI = "O892437".parse().unwrap();
+    assert_eq!(gridref.to_string_with_check(), "O8924376");
+    assert_eq!(OSI::from_string_with_check("o 892 437 6").unwrap(), gridref);
+}
+
+#[test]
+fn rejects_corrupted_check_character() {
+    // Transposed digits.
+    assert!(matches!(
+        OSI::from_string_with_check("O8924736"),
+        Err(Error::ParseError(_))
+    ));
+    // Mistyped check character.
+    assert!(matches!(
+        OSI::from_string_with_check("O892437Z"),
+        Err(Error::ParseError(_))
+    ));
+    assert_eq!(OSI::from_string_with_check(""), Err(Error::Empty));
+}
+
+#[test]
+fn divergence_precision_finds_finest_shared_cell() {
+    let gridref: OSI = "O892437".parse().unwrap();
+
+    // Agreeing at 10km, but differing at 1km.
+    let other: OSI = "O815478".parse().unwrap();
+    assert_eq!(gridref.divergence_precision(&other), Some(Precision::_10Km));
+    assert_ne!(
+        gridref.recalculate(Precision::_1Km),
+        other.recalculate(Precision::_1Km)
+    );
+
+    // Limited by the coarser of the two.
+    let other: OSI = "O8943".parse().unwrap();
+    assert_eq!(gridref.divergence_precision(&other), Some(Precision::_1Km));
+    assert_eq!(
+        gridref.divergence_precision(&gridref),
+        Some(Precision::_100M)
+    );
+
+    let other: OSI = "N892437".parse().unwrap();
+    assert_eq!(gridref.divergence_precision(&other), None);
+}
+
+#[test]
+fn cells_in_multipolygon_cover_both_parts() {
+    let part = |x: f64, y: f64, size: f64| {
+        Polygon::new(
+            LineString::from(vec![
+                (x, y),
+                (x + size, y),
+                (x + size, y + size),
+                (x, y + size),
+            ]),
+            vec![],
+        )
+    };
+    let area = MultiPolygon::new(vec![
+        part(389_000.0, 243_000.0, 2_000.0),
+        part(395_200.0, 245_200.0, 1_000.0),
+    ]);
+
+    let cells = OSI::cells_in_multipolygon(&area, Precision::_1Km).unwrap();
+    let cells: Vec<String> = cells.iter().map(|cell| cell.to_string()).collect();
+    assert_eq!(cells, vec!["O8943", "O9043", "O8944", "O9044", "O9545"]);
+
+    assert!(
+        OSI::cells_in_multipolygon(&MultiPolygon::new(vec![]), Precision::_1Km)
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        OSI::cells_in_multipolygon(
+            &MultiPolygon::new(vec![part(-500.0, 0.0, 1_000.0)]),
+            Precision::_1Km
+        ),
+        Err(Error::OutOfBounds)
+    );
+}
+
+#[test]
+fn ring_closed_repeats_first_coordinate() {
+    let gridref: OSI = "O892437".parse().unwrap();
+    let ring = gridref.ring_closed();
+
+    assert!(ring.is_closed());
+    assert_eq!(ring.0.len(), 5);
+    assert_eq!(ring.0.first(), ring.0.last());
+    assert_eq!(ring.0[0], gridref.sw().into());
+
+    // Closing the ring leaves the area unchanged.
+    let polygon = Polygon::new(ring, vec![]);
+    assert_eq!(polygon, gridref.perimeter());
+    assert_eq!(gridref.coverage_fraction(&polygon), 1.0);
+}